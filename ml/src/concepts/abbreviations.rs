@@ -0,0 +1,117 @@
+use crate::concepts::ahocorasick::AhoCorasick;
+
+/// Common abbreviations whose trailing period should not be mistaken for a
+/// sentence end.
+const DEFAULT_ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "etc", "inc", "ltd", "dept", "approx",
+    "fig", "eq", "vol", "no", "gen", "gov", "eg", "ie",
+];
+
+/// Common top-level domains, so the period right after a domain like
+/// "example.com" isn't taken for a sentence end.
+const DEFAULT_TLDS: &[&str] = &["com", "org", "net", "io", "edu", "gov", "co"];
+
+/// Recognizes abbreviations and TLDs immediately preceding a candidate
+/// sentence-ending punctuation mark, so a chunker's sentence-boundary search
+/// doesn't split "Dr. Smith" or "example.com" mid-term. Backed by an
+/// `AhoCorasick` automaton so an arbitrarily large dictionary costs no more
+/// per lookup than the small default one.
+pub struct AbbreviationMatcher {
+    automaton: AhoCorasick,
+}
+
+impl AbbreviationMatcher {
+    /// Builds a matcher over the default abbreviation/TLD dictionary.
+    pub fn new() -> Self {
+        Self::with_dictionary(
+            DEFAULT_ABBREVIATIONS
+                .iter()
+                .chain(DEFAULT_TLDS)
+                .map(|term| term.to_string()),
+        )
+    }
+
+    /// Builds a matcher over a caller-supplied dictionary, for callers that
+    /// need terms beyond the defaults.
+    pub fn with_dictionary(terms: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            automaton: AhoCorasick::new(terms),
+        }
+    }
+
+    /// Whether the word ending at `match_start` (the byte index of the `.`,
+    /// `!`, or `?` under consideration) in `text` is a known abbreviation or
+    /// TLD, and so should not be treated as a sentence end. A dotted word
+    /// such as "example.com" also matches when its last dot-separated part
+    /// is a known term. A single character of any kind (as in the initial
+    /// "J. Smith") is always treated as an abbreviation.
+    pub fn is_abbreviation(&self, text: &str, match_start: usize) -> bool {
+        let preceding = &text[..match_start];
+        let word_start = preceding
+            .rfind(|c: char| c.is_whitespace())
+            .map_or(0, |pos| pos + 1);
+        let word = &preceding[word_start..];
+
+        if word.is_empty() {
+            return false;
+        }
+        if word.chars().count() == 1 {
+            return true;
+        }
+
+        let lowered = word.to_lowercase();
+        let last_segment = lowered.rsplit('.').next().unwrap_or(lowered.as_str());
+        self.automaton.is_exact_match(&lowered) || self.automaton.is_exact_match(last_segment)
+    }
+}
+
+impl Default for AbbreviationMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognizes_default_abbreviation() {
+        let matcher = AbbreviationMatcher::new();
+        let text = "I spoke with Dr. Smith yesterday.";
+        let dot = text.find("Dr.").unwrap() + 2;
+        assert!(matcher.is_abbreviation(text, dot));
+    }
+
+    #[test]
+    fn test_recognizes_tld() {
+        let matcher = AbbreviationMatcher::new();
+        let text = "Details are at example.com. Take a look.";
+        let dot = text.find("example.com.").unwrap() + "example.com".len();
+        assert!(matcher.is_abbreviation(text, dot));
+    }
+
+    #[test]
+    fn test_single_letter_initial_is_abbreviation() {
+        let matcher = AbbreviationMatcher::new();
+        let text = "J. Smith arrived.";
+        let dot = text.find("J.").unwrap() + 1;
+        assert!(matcher.is_abbreviation(text, dot));
+    }
+
+    #[test]
+    fn test_real_sentence_end_is_not_abbreviation() {
+        let matcher = AbbreviationMatcher::new();
+        let text = "The meeting ended. Everyone left.";
+        let dot = text.find("ended.").unwrap() + "ended".len();
+        assert!(!matcher.is_abbreviation(text, dot));
+    }
+
+    #[test]
+    fn test_with_dictionary_accepts_custom_terms() {
+        let matcher = AbbreviationMatcher::with_dictionary(vec!["corp".to_string()]);
+        let text = "Acme Corp. filed the report.";
+        let dot = text.find("Corp.").unwrap() + 4;
+        assert!(matcher.is_abbreviation(text, dot));
+    }
+}
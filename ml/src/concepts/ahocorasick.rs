@@ -0,0 +1,161 @@
+use std::collections::{HashMap, VecDeque};
+
+/// A node in the pattern trie: one child per next character, a failure
+/// link (the index of the node reached by following the longest proper
+/// suffix of this node's path that is also in the trie), and the set of
+/// pattern indices that end here once failure-link outputs are unioned in.
+#[derive(Default)]
+struct TrieNode {
+    children: HashMap<char, usize>,
+    fail: usize,
+    output: Vec<usize>,
+}
+
+/// A multi-pattern exact-match automaton (Aho-Corasick). Built once over a
+/// dictionary of patterns, then scanned over arbitrary text in a single
+/// linear pass — the whole point being to replace an `O(patterns)` linear
+/// scan per candidate with an amortized `O(1)` transition per input
+/// character, regardless of how many patterns are in the dictionary.
+pub struct AhoCorasick {
+    nodes: Vec<TrieNode>,
+    patterns: Vec<String>,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton: a trie of `patterns`, then failure links
+    /// assigned by a BFS over the trie (each node's failure pointer is the
+    /// longest proper suffix of its path that is also a trie node), with
+    /// output sets unioned along those links so a match at a node reports
+    /// every pattern ending there, including shorter suffix patterns
+    /// reached only via the failure chain.
+    pub fn new(patterns: impl IntoIterator<Item = String>) -> Self {
+        let patterns: Vec<String> = patterns.into_iter().collect();
+        let mut nodes = vec![TrieNode::default()]; // node 0 is the root
+
+        for (pattern_index, pattern) in patterns.iter().enumerate() {
+            let mut current = 0;
+            for ch in pattern.chars() {
+                current = match nodes[current].children.get(&ch) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(TrieNode::default());
+                        let next = nodes.len() - 1;
+                        nodes[current].children.insert(ch, next);
+                        next
+                    }
+                };
+            }
+            nodes[current].output.push(pattern_index);
+        }
+
+        let mut queue = VecDeque::new();
+        let root_children: Vec<usize> = nodes[0].children.values().copied().collect();
+        for child in root_children {
+            nodes[child].fail = 0;
+            queue.push_back(child);
+        }
+
+        while let Some(current) = queue.pop_front() {
+            let children: Vec<(char, usize)> = nodes[current]
+                .children
+                .iter()
+                .map(|(&ch, &next)| (ch, next))
+                .collect();
+
+            for (ch, child) in children {
+                let mut fail = nodes[current].fail;
+                let fail_target = loop {
+                    if let Some(&next) = nodes[fail].children.get(&ch) {
+                        break next;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = nodes[fail].fail;
+                };
+
+                nodes[child].fail = fail_target;
+                let inherited = nodes[fail_target].output.clone();
+                nodes[child].output.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        Self { nodes, patterns }
+    }
+
+    /// Scans `text` once, returning every match as `(end_byte, pattern)`,
+    /// where `end_byte` is the byte offset immediately after the match.
+    pub fn find_iter<'a>(&'a self, text: &'a str) -> Vec<(usize, &'a str)> {
+        let mut matches = Vec::new();
+        let mut current = 0;
+
+        for (char_index, ch) in text.char_indices() {
+            loop {
+                if let Some(&next) = self.nodes[current].children.get(&ch) {
+                    current = next;
+                    break;
+                }
+                if current == 0 {
+                    break;
+                }
+                current = self.nodes[current].fail;
+            }
+
+            let end_byte = char_index + ch.len_utf8();
+            for &pattern_index in &self.nodes[current].output {
+                matches.push((end_byte, self.patterns[pattern_index].as_str()));
+            }
+        }
+
+        matches
+    }
+
+    /// Whether `text` is itself exactly one of the dictionary patterns,
+    /// rather than merely containing one as a substring.
+    pub fn is_exact_match(&self, text: &str) -> bool {
+        self.find_iter(text)
+            .iter()
+            .any(|&(end_byte, pattern)| end_byte == text.len() && pattern == text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(words: &[&str]) -> AhoCorasick {
+        AhoCorasick::new(words.iter().map(|w| w.to_string()))
+    }
+
+    #[test]
+    fn test_find_iter_matches_multiple_patterns_in_one_pass() {
+        let automaton = patterns(&["he", "she", "his", "hers"]);
+        let matches = automaton.find_iter("ushers");
+        let found: Vec<&str> = matches.iter().map(|&(_, pattern)| pattern).collect();
+        assert!(found.contains(&"she"));
+        assert!(found.contains(&"he"));
+        assert!(found.contains(&"hers"));
+    }
+
+    #[test]
+    fn test_is_exact_match() {
+        let automaton = patterns(&["dr", "mr", "com"]);
+        assert!(automaton.is_exact_match("dr"));
+        assert!(!automaton.is_exact_match("drive"));
+        assert!(!automaton.is_exact_match("address"));
+    }
+
+    #[test]
+    fn test_no_match_returns_empty() {
+        let automaton = patterns(&["dr", "mr"]);
+        assert!(automaton.find_iter("hello world").is_empty());
+    }
+
+    #[test]
+    fn test_empty_dictionary_matches_nothing() {
+        let automaton = patterns(&[]);
+        assert!(automaton.find_iter("anything").is_empty());
+        assert!(!automaton.is_exact_match("anything"));
+    }
+}
@@ -0,0 +1,365 @@
+use crate::concepts::AbbreviationMatcher;
+use crate::error::ApiError;
+use tree_sitter::{Language, Node, Parser};
+
+/// Default maximum size (in bytes) of a single chunk handed to the LLM.
+pub const DEFAULT_MAX_CHUNK_BYTES: usize = 2000;
+
+/// Overlap between consecutive chunks, as a fraction of `max_chunk_bytes`.
+/// Keeps concepts that straddle a chunk boundary from being dropped.
+const OVERLAP_RATIO: f32 = 0.12;
+
+/// A slice of the source document handed to the concept extractor, tagged
+/// with the byte range it came from so results can be attributed back to a
+/// document region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChunk {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Finds the largest byte index `<= index` that lands on a valid UTF-8 char
+/// boundary, so a hard cut never panics on a multi-byte character.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut i = index.min(s.len());
+    while i > 0 && !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+/// Finds the best natural cut point within `window`, preferring (in order)
+/// a paragraph break, a sentence end, then a plain whitespace boundary.
+/// Falls back to the full window length if none of those are found. A
+/// candidate sentence end is skipped when `abbreviations` recognizes the
+/// word immediately before it (e.g. "Dr." or "example.com"), so chunking
+/// doesn't split mid-abbreviation.
+fn find_boundary(window: &str, abbreviations: &AbbreviationMatcher) -> usize {
+    if let Some(pos) = window.rfind("\n\n") {
+        return pos;
+    }
+
+    let mut best_sentence: Option<usize> = None;
+    for (idx, ch) in window.char_indices() {
+        if matches!(ch, '.' | '!' | '?') {
+            let next_is_boundary = window[idx + ch.len_utf8()..]
+                .chars()
+                .next()
+                .map_or(true, |c| c.is_whitespace());
+            if next_is_boundary && !abbreviations.is_abbreviation(window, idx) {
+                best_sentence = Some(idx + ch.len_utf8());
+            }
+        }
+    }
+    if let Some(pos) = best_sentence {
+        return pos;
+    }
+
+    if let Some(pos) = window.rfind(char::is_whitespace) {
+        if pos > 0 {
+            return pos;
+        }
+    }
+
+    window.len()
+}
+
+/// Splits `text` into overlapping chunks of at most `max_chunk_bytes` bytes,
+/// cut on paragraph/sentence/whitespace boundaries where possible and always
+/// on a valid `char` boundary. Each chunk carries the byte range it spans in
+/// the original text.
+pub fn chunk_text(text: &str, max_chunk_bytes: usize) -> Vec<TextChunk> {
+    if text.len() <= max_chunk_bytes {
+        return vec![TextChunk {
+            text: text.to_string(),
+            start: 0,
+            end: text.len(),
+        }];
+    }
+
+    let overlap = ((max_chunk_bytes as f32) * OVERLAP_RATIO) as usize;
+    let abbreviations = AbbreviationMatcher::new();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < text.len() {
+        let remaining = text.len() - start;
+        if remaining <= max_chunk_bytes {
+            chunks.push(TextChunk {
+                text: text[start..].to_string(),
+                start,
+                end: text.len(),
+            });
+            break;
+        }
+
+        let window_end = floor_char_boundary(text, start + max_chunk_bytes);
+        let window = &text[start..window_end];
+        let boundary = find_boundary(window, &abbreviations).max(1);
+        let end = start + boundary;
+
+        chunks.push(TextChunk {
+            text: text[start..end].to_string(),
+            start,
+            end,
+        });
+
+        let next_start = if end > overlap {
+            floor_char_boundary(text, end - overlap)
+        } else {
+            end
+        };
+
+        // Guarantee forward progress even if the boundary search and
+        // overlap window land on the same spot.
+        start = if next_start <= start { end } else { next_start };
+    }
+
+    chunks
+}
+
+/// Node kinds, across the tree-sitter grammars this crate is likely to see,
+/// that mark an "outline" boundary (functions, classes, blocks). A candidate
+/// split point nested inside more of these is a worse place to cut than one
+/// nested inside fewer, since it more likely falls inside a function/class
+/// body rather than between top-level items.
+const OUTLINE_NODE_KINDS: &[&str] = &[
+    "function_item",
+    "function_definition",
+    "function_declaration",
+    "method_definition",
+    "class_definition",
+    "class_declaration",
+    "impl_item",
+    "struct_item",
+    "block",
+    "compound_statement",
+];
+
+/// A place `chunk_code` could cut, tagged with how many enclosing outline
+/// nodes (functions/classes/blocks) it sits inside. Lower is better: depth 0
+/// sits between top-level items rather than mid-body.
+struct CodeBoundary {
+    byte: usize,
+    outline_depth: usize,
+}
+
+fn is_outline_node(node: &Node) -> bool {
+    OUTLINE_NODE_KINDS.contains(&node.kind())
+}
+
+/// A boundary is only useful if it lands at the start of a line, so a split
+/// there never breaks a line of code in half.
+fn at_line_start(source: &[u8], byte: usize) -> bool {
+    byte == 0 || source.get(byte - 1) == Some(&b'\n')
+}
+
+/// Walks the parse tree collecting every node boundary that lands at the
+/// start of a line, tagged with its nesting depth inside outline nodes.
+fn collect_boundaries(node: Node, source: &[u8], outline_depth: usize, out: &mut Vec<CodeBoundary>) {
+    if at_line_start(source, node.start_byte()) {
+        out.push(CodeBoundary {
+            byte: node.start_byte(),
+            outline_depth,
+        });
+    }
+    if at_line_start(source, node.end_byte()) {
+        out.push(CodeBoundary {
+            byte: node.end_byte(),
+            outline_depth,
+        });
+    }
+
+    let child_depth = if is_outline_node(&node) {
+        outline_depth + 1
+    } else {
+        outline_depth
+    };
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_boundaries(child, source, child_depth, out);
+    }
+}
+
+/// Picks the best boundary within `(start, window_end]`: the one nested
+/// inside the fewest outline nodes, breaking ties by proximity to
+/// `window_end`. Falls back to `find_boundary` on the windowed slice when no
+/// syntactic boundary exists within the window.
+fn find_code_boundary(
+    boundaries: &[CodeBoundary],
+    start: usize,
+    window_end: usize,
+    window: &str,
+    abbreviations: &AbbreviationMatcher,
+) -> usize {
+    boundaries
+        .iter()
+        .filter(|b| b.byte > start && b.byte <= window_end)
+        .min_by_key(|b| (b.outline_depth, window_end - b.byte))
+        .map(|b| b.byte - start)
+        .unwrap_or_else(|| find_boundary(window, abbreviations))
+}
+
+/// Like `chunk_text`, but for source code: drives chunk boundaries off a
+/// tree-sitter parse of `text` instead of punctuation, so splits land between
+/// top-level items (functions, classes) rather than mid-body wherever
+/// possible. Falls back to the same text heuristics as `chunk_text` whenever
+/// no syntactic boundary exists within the window, and keeps the same
+/// overlap semantics (expressed here in bytes via `overlap` rather than a
+/// fixed ratio, since callers already know the language's natural unit size).
+pub fn chunk_code(
+    text: &str,
+    language: Language,
+    chunk_size: usize,
+    overlap: usize,
+) -> Result<Vec<TextChunk>, ApiError> {
+    if text.len() <= chunk_size {
+        return Ok(vec![TextChunk {
+            text: text.to_string(),
+            start: 0,
+            end: text.len(),
+        }]);
+    }
+
+    let mut parser = Parser::new();
+    parser.set_language(&language).map_err(|e| {
+        ApiError::InternalError(format!("Failed to load tree-sitter grammar: {}", e))
+    })?;
+    let tree = parser
+        .parse(text, None)
+        .ok_or_else(|| ApiError::InternalError("Failed to parse source for chunking".to_string()))?;
+
+    let source = text.as_bytes();
+    let mut boundaries = Vec::new();
+    collect_boundaries(tree.root_node(), source, 0, &mut boundaries);
+
+    let abbreviations = AbbreviationMatcher::new();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < text.len() {
+        let remaining = text.len() - start;
+        if remaining <= chunk_size {
+            chunks.push(TextChunk {
+                text: text[start..].to_string(),
+                start,
+                end: text.len(),
+            });
+            break;
+        }
+
+        let window_end = floor_char_boundary(text, start + chunk_size);
+        let window = &text[start..window_end];
+        let boundary =
+            find_code_boundary(&boundaries, start, window_end, window, &abbreviations).max(1);
+        let end = start + boundary;
+
+        chunks.push(TextChunk {
+            text: text[start..end].to_string(),
+            start,
+            end,
+        });
+
+        let next_start = if end > overlap {
+            floor_char_boundary(text, end - overlap)
+        } else {
+            end
+        };
+
+        start = if next_start <= start { end } else { next_start };
+    }
+
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_is_single_chunk() {
+        let chunks = chunk_text("Hello world.", 500);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "Hello world.");
+        assert_eq!(chunks[0].start, 0);
+        assert_eq!(chunks[0].end, 12);
+    }
+
+    #[test]
+    fn test_long_text_splits_into_multiple_chunks() {
+        let text = "word ".repeat(1000);
+        let chunks = chunk_text(&text, 500);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.last().unwrap().end, text.len());
+    }
+
+    #[test]
+    fn test_chunks_overlap() {
+        let text = "word ".repeat(1000);
+        let chunks = chunk_text(&text, 500);
+        for pair in chunks.windows(2) {
+            assert!(pair[1].start < pair[0].end, "expected overlapping ranges");
+        }
+    }
+
+    #[test]
+    fn test_multibyte_text_does_not_panic() {
+        let text = "🌍".repeat(500);
+        let chunks = chunk_text(&text, 500);
+        assert!(!chunks.is_empty());
+        for chunk in &chunks {
+            let _ = chunk.text.len(); // would panic on invalid UTF-8 boundary
+        }
+    }
+
+    #[test]
+    fn test_chunk_text_does_not_split_on_abbreviation() {
+        let filler = "word ".repeat(90);
+        let text = format!("{}Dr. Smith wrote the report. It was thorough.", filler);
+        let chunks = chunk_text(&text, filler.len() + 10);
+        let split_mid_abbreviation = chunks
+            .iter()
+            .any(|chunk| chunk.text.trim_end().ends_with("Dr"));
+        assert!(!split_mid_abbreviation, "chunk boundary split \"Dr.\" as a sentence end");
+    }
+
+    fn rust_functions(count: usize) -> String {
+        (0..count)
+            .map(|i| format!("fn f{0}() {{\n    let x = {0};\n    println!(\"{{}}\", x);\n}}\n\n", i))
+            .collect()
+    }
+
+    #[test]
+    fn test_chunk_code_short_text_is_single_chunk() {
+        let text = "fn main() {}";
+        let chunks = chunk_code(text, tree_sitter_rust::language(), 500, 50).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, text);
+    }
+
+    #[test]
+    fn test_chunk_code_splits_between_functions() {
+        let text = rust_functions(40);
+        let chunks = chunk_code(&text, tree_sitter_rust::language(), 300, 30).unwrap();
+        assert!(chunks.len() > 1);
+        // Chunks after the first start `overlap` bytes before the previous
+        // cut, so it is the cut points (chunk ends) that land between
+        // functions.
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(
+                text[chunk.end..].starts_with("fn "),
+                "expected chunk to end at a function boundary, got: {:?}",
+                chunk.text
+            );
+        }
+    }
+
+    #[test]
+    fn test_chunk_code_covers_whole_input() {
+        let text = rust_functions(40);
+        let chunks = chunk_code(&text, tree_sitter_rust::language(), 300, 30).unwrap();
+        assert_eq!(chunks.last().unwrap().end, text.len());
+    }
+}
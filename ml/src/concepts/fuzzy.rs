@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+/// A node in the ordered trie of concept strings. Plays the role of an FST
+/// here: a sorted, prefix-shared set of terms that a Levenshtein automaton
+/// can be intersected with in a single traversal, without per-term distance
+/// computation.
+#[derive(Default)]
+struct TrieNode {
+    children: BTreeMap<char, usize>,
+    /// Indices into the original `concepts` slice whose normalized string
+    /// ends exactly at this node (more than one when concepts normalize to
+    /// the same string).
+    terms: Vec<usize>,
+}
+
+/// An ordered set of strings, keyed for traversal alongside a Levenshtein
+/// automaton. Construction is the only place normalization happens; lookups
+/// never touch the original strings again.
+struct ConceptTrie {
+    nodes: Vec<TrieNode>,
+}
+
+impl ConceptTrie {
+    fn build(normalized: &[String]) -> Self {
+        let mut nodes = vec![TrieNode::default()];
+
+        for (index, term) in normalized.iter().enumerate() {
+            let mut current = 0;
+            for ch in term.chars() {
+                current = match nodes[current].children.get(&ch) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(TrieNode::default());
+                        let next = nodes.len() - 1;
+                        nodes[current].children.insert(ch, next);
+                        next
+                    }
+                };
+            }
+            nodes[current].terms.push(index);
+        }
+
+        Self { nodes }
+    }
+}
+
+/// A Levenshtein automaton for a single query string and maximum edit
+/// distance `k`. States are the standard dynamic-programming distance row:
+/// `row[j]` is the edit distance between the document prefix consumed so
+/// far and `query[0..j]`. Stepping the automaton on an input character
+/// produces the next row in constant time per query character.
+struct LevenshteinAutomaton {
+    query: Vec<char>,
+    max_distance: usize,
+}
+
+impl LevenshteinAutomaton {
+    fn new(query: &str, max_distance: usize) -> Self {
+        Self {
+            query: query.chars().collect(),
+            max_distance,
+        }
+    }
+
+    /// The row before any document characters have been consumed:
+    /// `row[j] = j`, the cost of inserting the first `j` query characters.
+    fn start(&self) -> Vec<usize> {
+        (0..=self.query.len()).collect()
+    }
+
+    /// Consumes one document character, returning the next row.
+    fn step(&self, row: &[usize], ch: char) -> Vec<usize> {
+        let mut next_row = Vec::with_capacity(row.len());
+        next_row.push(row[0] + 1);
+
+        for (i, &query_char) in self.query.iter().enumerate() {
+            let substitution_cost = if query_char == ch { 0 } else { 1 };
+            let value = (row[i + 1] + 1)
+                .min(next_row[i] + 1)
+                .min(row[i] + substitution_cost);
+            next_row.push(value);
+        }
+
+        next_row
+    }
+
+    /// Whether no extension of the document so far can land within
+    /// `max_distance` of the query. Once every entry in the row exceeds the
+    /// bound, no further characters can bring it back down, so the trie
+    /// branch can be pruned.
+    fn is_prunable(&self, row: &[usize]) -> bool {
+        match row.iter().min() {
+            Some(&min) => min > self.max_distance,
+            None => true,
+        }
+    }
+
+    /// Whether the document consumed so far (in full) is within
+    /// `max_distance` of the query.
+    fn is_accepting(&self, row: &[usize]) -> bool {
+        match row.last() {
+            Some(&distance) => distance <= self.max_distance,
+            None => false,
+        }
+    }
+}
+
+/// Finds every index whose normalized string is within `automaton`'s bound
+/// of its query, by walking `trie` and `automaton` together one character at
+/// a time, pruning branches the automaton rules out.
+fn collect_matches(trie: &ConceptTrie, automaton: &LevenshteinAutomaton, out: &mut Vec<usize>) {
+    fn walk(
+        trie: &ConceptTrie,
+        automaton: &LevenshteinAutomaton,
+        node: usize,
+        row: &[usize],
+        out: &mut Vec<usize>,
+    ) {
+        if automaton.is_accepting(row) {
+            out.extend(&trie.nodes[node].terms);
+        }
+
+        for (&ch, &child) in &trie.nodes[node].children {
+            let next_row = automaton.step(row, ch);
+            if !automaton.is_prunable(&next_row) {
+                walk(trie, automaton, child, &next_row, out);
+            }
+        }
+    }
+
+    walk(trie, automaton, 0, &automaton.start(), out);
+}
+
+/// A minimal union-find over concept indices, used to collapse the pairwise
+/// matches `collect_matches` reports into connected components.
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        if self.parent[index] != index {
+            self.parent[index] = self.find(self.parent[index]);
+        }
+        self.parent[index]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a != root_b {
+            self.parent[root_a] = root_b;
+        }
+    }
+}
+
+/// Groups `concepts` by bounded edit distance: builds a trie (ordered set)
+/// over the concept strings, and for each one constructs a Levenshtein
+/// automaton for `max_edit_distance` to enumerate every existing term within
+/// that distance in a single traversal, unioning matches into the same
+/// group. `max_edit_distance` of `0` only groups exact duplicates (after
+/// case-folding); `1` or `2` additionally catches typos and minor surface
+/// variation. Returns each group as the set of original indices it covers;
+/// every index appears in exactly one group, including singletons.
+pub fn fuzzy_duplicate_groups(concepts: &[String], max_edit_distance: usize) -> Vec<Vec<usize>> {
+    let normalized: Vec<String> = concepts.iter().map(|c| c.trim().to_lowercase()).collect();
+    let trie = ConceptTrie::build(&normalized);
+    let mut sets = DisjointSet::new(concepts.len());
+
+    for (index, term) in normalized.iter().enumerate() {
+        let automaton = LevenshteinAutomaton::new(term, max_edit_distance);
+        let mut matches = Vec::new();
+        collect_matches(&trie, &automaton, &mut matches);
+
+        for matched_index in matches {
+            sets.union(index, matched_index);
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for index in 0..concepts.len() {
+        let root = sets.find(index);
+        groups.entry(root).or_default().push(index);
+    }
+
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group_containing(groups: &[Vec<usize>], index: usize) -> &[usize] {
+        groups
+            .iter()
+            .find(|group| group.contains(&index))
+            .expect("every index should appear in exactly one group")
+    }
+
+    #[test]
+    fn test_exact_duplicates_group_at_distance_zero() {
+        let concepts = vec!["Color".to_string(), "color".to_string(), "shape".to_string()];
+        let groups = fuzzy_duplicate_groups(&concepts, 0);
+        assert_eq!(group_containing(&groups, 0), group_containing(&groups, 1));
+        assert_ne!(group_containing(&groups, 0), group_containing(&groups, 2));
+    }
+
+    #[test]
+    fn test_typo_groups_at_distance_one() {
+        let concepts = vec!["color".to_string(), "colour".to_string(), "shape".to_string()];
+        let groups = fuzzy_duplicate_groups(&concepts, 1);
+        let group = group_containing(&groups, 0);
+        assert!(group.contains(&1));
+        assert!(!group.contains(&2));
+    }
+
+    #[test]
+    fn test_distance_zero_does_not_group_typos() {
+        let concepts = vec!["color".to_string(), "colour".to_string()];
+        let groups = fuzzy_duplicate_groups(&concepts, 0);
+        assert_ne!(group_containing(&groups, 0), group_containing(&groups, 1));
+    }
+
+    #[test]
+    fn test_every_index_appears_exactly_once() {
+        let concepts = vec!["alpha".to_string(), "beta".to_string(), "gamma".to_string()];
+        let groups = fuzzy_duplicate_groups(&concepts, 1);
+        let total: usize = groups.iter().map(|g| g.len()).sum();
+        assert_eq!(total, concepts.len());
+    }
+}
@@ -0,0 +1,11 @@
+pub mod abbreviations;
+pub mod ahocorasick;
+pub mod chunking;
+pub mod fuzzy;
+pub mod model;
+pub mod nlp;
+pub use abbreviations::AbbreviationMatcher;
+pub use ahocorasick::AhoCorasick;
+pub use fuzzy::fuzzy_duplicate_groups;
+pub use model::*;
+pub use nlp::{CandidateKeyword, KeywordExtractor};
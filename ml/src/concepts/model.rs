@@ -1,13 +1,24 @@
+use crate::concepts::chunking::{chunk_text, DEFAULT_MAX_CHUNK_BYTES};
+use crate::concepts::nlp::KeywordExtractor;
 use crate::error::ApiError;
-use log::{debug, info};
+use crate::retry::{backoff_delay, classify_error, RetryDecision, DEFAULT_MAX_ATTEMPTS};
+use futures::future::join_all;
+use log::{debug, info, warn};
 use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Concept {
     pub concept: String,
+    /// Byte range in the source document this concept was extracted from,
+    /// when it's known (absent for concepts loaded back from storage).
+    #[serde(default)]
+    pub source_range: Option<(usize, usize)>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -59,10 +70,20 @@ struct OllamaOptions {
 struct OllamaResponse {
     response: String,
 }
+/// Default number of chunk extraction calls `generate_concepts` keeps in
+/// flight at once, so a long document doesn't thundering-herd a single
+/// Ollama instance.
+pub const DEFAULT_CHUNK_PARALLELISM: usize = 4;
+
+/// Default ceiling on retry attempts for a single chunk's LLM call.
+pub const DEFAULT_CHUNK_MAX_ATTEMPTS: u32 = DEFAULT_MAX_ATTEMPTS;
+
 pub struct ConceptsModel {
     base_url: String,
     client: Client,
     model: String,
+    chunk_parallelism: usize,
+    max_attempts: u32,
 }
 
 impl ConceptsModel {
@@ -76,9 +97,25 @@ impl ConceptsModel {
             base_url: base_url.to_string(),
             client,
             model: "phi3.5".to_string(),
+            chunk_parallelism: DEFAULT_CHUNK_PARALLELISM,
+            max_attempts: DEFAULT_CHUNK_MAX_ATTEMPTS,
         }
     }
 
+    /// Overrides how many chunk extraction calls `generate_concepts` keeps
+    /// in flight at once.
+    pub fn with_chunk_parallelism(mut self, chunk_parallelism: usize) -> Self {
+        self.chunk_parallelism = chunk_parallelism.max(1);
+        self
+    }
+
+    /// Overrides how many attempts in total (the first call included) a
+    /// single chunk's LLM call gets on transient failures before giving up.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
     pub fn clean_text(&self, text: &str) -> String {
         let re_punct: Regex = Regex::new(r"[^\w\s']").unwrap();
         let text = re_punct.replace_all(text, " ");
@@ -105,25 +142,19 @@ impl ConceptsModel {
         lemmatized_words.join(" ")
     }
 
-    pub async fn generate_concepts(&self, text: &str) -> Result<Vec<Concept>, ApiError> {
+    fn build_request(&self, truncated_text: &str) -> OllamaRequest {
         let system_prompt = r#"You are a concept extractor that MUST:
         1. Extract key concepts from the text
         2. Output ONLY simple concepts separated by commas (NO bullet points, NO descriptions)
         4. Example output:
             Happy Prince, Golden Statue, Ruby Sword, Sapphire Eyes, Town Councillors
-        
+
         DO NOT include:
         - Bullet points (-)
         - Descriptions or explanations
         - Newlines
         - Colons or semicolons"#;
 
-        let truncated_text = if text.len() > 500 {
-            format!("{}...", &text[..500])
-        } else {
-            text.to_string()
-        };
-
         let template = format!(
             "Extract 5-10 key concepts from this text as simple words or short phrases separated by commas ONLY: {}",
             truncated_text
@@ -137,42 +168,83 @@ impl ConceptsModel {
             },
             required: ["concepts".to_string()].to_vec(),
         };
-        info!("Requesting concepts using model: {}", self.model);
-        let request = OllamaRequest {
+
+        OllamaRequest {
             model: self.model.clone(),
             prompt: template,
             system: system_prompt.to_string(),
             options: OllamaOptions { temperature: 0.0 },
-            format: format,
+            format,
             stream: false,
-        };
+        }
+    }
 
-        let url = format!("{}/api/generate", self.base_url);
-        debug!("Sending request to: {}", url);
-        info!("Request body: {:?}", request);
+    /// Extracts concepts from the whole document, chunking it first when it
+    /// exceeds `DEFAULT_MAX_CHUNK_BYTES` so nothing past the first chunk is
+    /// silently dropped. Chunks are processed concurrently, bounded by
+    /// `chunk_parallelism` in-flight LLM calls at once; each chunk's result
+    /// is collected independently so one chunk exhausting its retries
+    /// (a transient failure, or a response the model can't parse) only
+    /// loses that chunk's concepts rather than the whole document's.
+    /// Surviving chunks' concepts are merged, deduplicating by stemmed name
+    /// (the same stem-based matching `KeywordExtractor` uses) so
+    /// overlapping chunks don't yield near-duplicate concepts that only
+    /// differ by inflection.
+    pub async fn generate_concepts(&self, text: &str) -> Result<Vec<Concept>, ApiError> {
+        let chunks = chunk_text(text, DEFAULT_MAX_CHUNK_BYTES);
+        if chunks.len() > 1 {
+            info!(
+                "Text length {} exceeds {} bytes, processing {} chunks",
+                text.len(),
+                DEFAULT_MAX_CHUNK_BYTES,
+                chunks.len()
+            );
+        }
 
-        let response = self
-            .client
-            .post(url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                info!("Error requesting concepts: {}", e);
-                ApiError::RequestError(e)
-            })?;
+        let semaphore = Arc::new(Semaphore::new(self.chunk_parallelism));
+        let per_chunk = join_all(chunks.iter().map(|chunk| {
+            let semaphore = Arc::clone(&semaphore);
+            async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("chunk extraction semaphore was closed");
+                self.extract_from_chunk(chunk).await
+            }
+        }))
+        .await;
 
-        let body: String = response.text().await.map_err(|e| {
-            info!("Error extracting response text: {}", e);
-            ApiError::RequestError(e)
-        })?;
+        let extractor = KeywordExtractor::new();
+        let mut seen_stems = HashSet::new();
+        let mut concepts = Vec::new();
+        for (index, result) in per_chunk.into_iter().enumerate() {
+            let chunk_concepts = match result {
+                Ok(chunk_concepts) => chunk_concepts,
+                Err(e) => {
+                    warn!("Skipping chunk {} after extraction failed: {:?}", index, e);
+                    continue;
+                }
+            };
 
-        info!("Raw response: {}", body);
+            for concept in chunk_concepts {
+                let stem = extractor.stem_phrase(&concept.concept.to_lowercase());
+                if seen_stems.insert(stem) {
+                    concepts.push(concept);
+                }
+            }
+        }
 
-        let ollama_response: OllamaResponse = serde_json::from_str(&body).map_err(|e| {
-            info!("Error parsing response JSON: {}", e);
-            ApiError::InternalError(format!("JSON parse error: {}", e))
-        })?;
+        debug!("Lemmatized concepts: {:?}", concepts);
+        Ok(concepts)
+    }
+
+    /// Runs concept extraction on a single chunk, tagging each resulting
+    /// concept with the chunk's source byte range.
+    async fn extract_from_chunk(
+        &self,
+        chunk: &crate::concepts::chunking::TextChunk,
+    ) -> Result<Vec<Concept>, ApiError> {
+        let ollama_response = self.generate_with_retry(&chunk.text).await?;
 
         #[derive(Debug, Deserialize)]
         struct ConceptsResponse {
@@ -181,22 +253,112 @@ impl ConceptsModel {
 
         let concepts_response: ConceptsResponse = serde_json::from_str(&ollama_response.response)
             .map_err(|e| {
-            info!("Error parsing nested JSON: {}", e);
-            ApiError::InternalError(format!("Failed to parse concepts JSON: {}", e))
-        })?;
+                info!("Error parsing nested JSON: {}", e);
+                ApiError::InternalError(format!("Failed to parse concepts JSON: {}", e))
+            })?;
 
         let mut concepts: Vec<Concept> = Vec::new();
         for concept in concepts_response.concepts {
             let concept = concept.trim();
             if !concept.is_empty() && concept.split_whitespace().count() <= 3 {
-                let lemmatized = self.lemmatize_concept(&concept);
+                let lemmatized = self.lemmatize_concept(concept);
                 concepts.push(Concept {
                     concept: lemmatized,
+                    source_range: Some((chunk.start, chunk.end)),
                 });
             }
         }
 
-        debug!("Lemmatized concepts: {:?}", concepts);
         Ok(concepts)
     }
+
+    /// Calls the LLM, retrying transient failures with exponential backoff.
+    /// If the model rejects the prompt as too long, it is progressively
+    /// truncated and resubmitted rather than waited out.
+    async fn generate_with_retry(&self, text: &str) -> Result<OllamaResponse, ApiError> {
+        let mut prompt_text = text.to_string();
+        let mut last_err: Option<ApiError> = None;
+
+        for attempt in 1..=self.max_attempts {
+            match self.post_generate(&prompt_text).await {
+                Ok(response) => return Ok(response),
+                Err((decision, err)) => {
+                    if attempt == self.max_attempts || decision == RetryDecision::GiveUp {
+                        return Err(err);
+                    }
+
+                    warn!(
+                        "Concept generation failed ({:?}) on attempt {}/{}: {}",
+                        decision, attempt, self.max_attempts, err
+                    );
+
+                    if decision == RetryDecision::RetryTokenized {
+                        let half = prompt_text.len() / 2;
+                        let mut end = half;
+                        while end > 0 && !prompt_text.is_char_boundary(end) {
+                            end -= 1;
+                        }
+                        prompt_text.truncate(end);
+                    }
+
+                    last_err = Some(err);
+                    tokio::time::sleep(backoff_delay(attempt, decision)).await;
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or(ApiError::InternalError(
+            "Concept generation exhausted retries".to_string(),
+        )))
+    }
+
+    /// Single POST attempt, returning the classified retry decision alongside
+    /// the error so `generate_with_retry` can decide what to do next.
+    async fn post_generate(
+        &self,
+        truncated_text: &str,
+    ) -> Result<OllamaResponse, (RetryDecision, ApiError)> {
+        let request = self.build_request(truncated_text);
+
+        let url = format!("{}/api/generate", self.base_url);
+        debug!("Sending request to: {}", url);
+        info!("Requesting concepts using model: {}", self.model);
+        info!("Request body: {:?}", request);
+
+        let response = self
+            .client
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                info!("Error requesting concepts: {}", e);
+                (RetryDecision::Retry, ApiError::RequestError(e))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let decision = classify_error(Some(status), &body);
+            return Err((
+                decision,
+                ApiError::InternalError(format!("Error {}: {}", status, body)),
+            ));
+        }
+
+        let body: String = response.text().await.map_err(|e| {
+            info!("Error extracting response text: {}", e);
+            (RetryDecision::Retry, ApiError::RequestError(e))
+        })?;
+
+        info!("Raw response: {}", body);
+
+        serde_json::from_str::<OllamaResponse>(&body).map_err(|e| {
+            info!("Error parsing response JSON: {}", e);
+            (
+                RetryDecision::Retry,
+                ApiError::InternalError(format!("JSON parse error: {}", e)),
+            )
+        })
+    }
 }
@@ -1,5 +1,10 @@
+use crate::data::cdn::storage::TextStorage;
 use crate::error::ApiError;
-use log::info;
+use crate::retry::{backoff_delay, classify_error, RetryDecision, DEFAULT_MAX_ATTEMPTS};
+use async_trait::async_trait;
+use log::{info, warn};
+use sha1::{Digest, Sha1};
+use std::time::Duration;
 
 const TEXTS_REPO: &str = "oort-cdn";
 const MAIN_REPO: &str = "oort";
@@ -30,70 +35,224 @@ impl GitHubCDN {
         }
     }
 
-    pub async fn upload_text(&self, content: &str, filename: &str) -> Result<String, ApiError> {
-        info!("GitHub upload config - owner: '{}', repo: '{}', token_present: {}", 
-              self.owner, self.repo, !self.token.is_empty());
-        
-        if self.owner.is_empty() || self.token.is_empty() {
-            return Err(ApiError::InternalError("GitHub owner or token not configured".to_string()));
-        }
-        
-        let encoded_content = base64::encode(content);
-        
-        let url = format!(
+    fn contents_url(&self, filename: &str) -> String {
+        format!(
             "https://api.github.com/repos/{}/{}/contents/texts/{}",
             self.owner, self.repo, filename
-        );
-        
-        info!("GitHub upload URL: {}", url);
+        )
+    }
 
-        // First, check if the file already exists to get its SHA
-        let existing_response = self.client
-            .get(&url)
+    async fn get_contents(&self, filename: &str) -> Result<Option<serde_json::Value>, ApiError> {
+        let response = self.client
+            .get(self.contents_url(filename))
             .header("Authorization", format!("Bearer {}", self.token))
             .header("Accept", "application/vnd.github.v3+json")
             .header("User-Agent", "OortML")
             .send()
             .await?;
 
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        Ok(response.json::<serde_json::Value>().await.ok())
+    }
+
+    /// Issues the PUT, retrying with exponential backoff on transient
+    /// failures and honoring GitHub's rate-limit signals (`403`/`429` with
+    /// `Retry-After`/`X-RateLimit-Reset`) instead of surfacing them as an
+    /// opaque error.
+    async fn put_contents_with_retry(
+        &self,
+        url: &str,
+        payload: &serde_json::Value,
+    ) -> Result<reqwest::Response, ApiError> {
+        let mut last_err: Option<ApiError> = None;
+
+        for attempt in 1..=DEFAULT_MAX_ATTEMPTS {
+            let response = self.client
+                .put(url)
+                .header("Authorization", format!("Bearer {}", self.token))
+                .header("Accept", "application/vnd.github.v3+json")
+                .header("User-Agent", "OortML")
+                .json(payload)
+                .send()
+                .await?;
+
+            info!("GitHub upload response status: {}", response.status());
+            if response.status().is_success() {
+                return Ok(response);
+            }
+
+            let status = response.status();
+            let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                || (status == reqwest::StatusCode::FORBIDDEN
+                    && response
+                        .headers()
+                        .get("X-RateLimit-Remaining")
+                        .and_then(|v| v.to_str().ok())
+                        == Some("0"));
+            let rate_limit_wait = rate_limited.then(|| rate_limit_delay(&response, attempt));
+
+            let body = response.text().await.unwrap_or_default();
+            let decision = if rate_limited {
+                RetryDecision::RetryAfterRateLimit
+            } else {
+                classify_error(Some(status), &body)
+            };
+            let delay = rate_limit_wait.unwrap_or_else(|| backoff_delay(attempt, decision));
+            let err = ApiError::InternalError(format!("GitHub upload failed: {}", body));
+
+            if attempt == DEFAULT_MAX_ATTEMPTS || decision == RetryDecision::GiveUp {
+                return Err(err);
+            }
+
+            warn!(
+                "GitHub upload failed ({:?}) on attempt {}/{}: {}",
+                decision, attempt, DEFAULT_MAX_ATTEMPTS, err
+            );
+            last_err = Some(err);
+            tokio::time::sleep(delay).await;
+        }
+
+        Err(last_err.unwrap_or(ApiError::InternalError(
+            "GitHub upload exhausted retries".to_string(),
+        )))
+    }
+}
+
+/// Computes the git blob SHA-1 for `content` (`blob <len>\0<content>`), the
+/// same hash GitHub reports as an existing file's `sha`, so an unchanged
+/// upload can be skipped instead of re-committed.
+fn git_blob_sha1(content: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(format!("blob {}\0", content.len()));
+    hasher.update(content);
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Reads how long to back off from a rate-limited GitHub response,
+/// preferring `Retry-After`, then `X-RateLimit-Reset` (a Unix timestamp),
+/// and falling back to the exponential rate-limit backoff if neither header
+/// is present.
+fn rate_limit_delay(response: &reqwest::Response, attempt: u32) -> Duration {
+    if let Some(retry_after) = response
+        .headers()
+        .get("Retry-After")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        return Duration::from_secs(retry_after);
+    }
+
+    if let Some(reset_at) = response
+        .headers()
+        .get("X-RateLimit-Reset")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if reset_at > now {
+            return Duration::from_secs(reset_at - now);
+        }
+    }
+
+    backoff_delay(attempt, RetryDecision::RetryAfterRateLimit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_git_blob_sha1_matches_git_hash_object() {
+        // `echo "hello world" | git hash-object --stdin`
+        assert_eq!(
+            git_blob_sha1("hello world\n"),
+            "3b18e512dba79e4c8300dd08aeb37f8e728b8dad"
+        );
+    }
+}
+
+#[async_trait]
+impl TextStorage for GitHubCDN {
+    async fn upload_text(&self, content: &str, filename: &str) -> Result<String, ApiError> {
+        info!("GitHub upload config - owner: '{}', repo: '{}', token_present: {}",
+              self.owner, self.repo, !self.token.is_empty());
+
+        if self.owner.is_empty() || self.token.is_empty() {
+            return Err(ApiError::InternalError("GitHub owner or token not configured".to_string()));
+        }
+
+        let encoded_content = base64::encode(content);
+        let url = self.contents_url(filename);
+        info!("GitHub upload URL: {}", url);
+
+        // First, check if the file already exists to get its SHA
+        let existing_content = self.get_contents(filename).await?;
+        let local_sha = git_blob_sha1(content);
+
         let mut payload = serde_json::json!({
             "message": format!("Add text: {}", filename),
             "content": encoded_content,
             "branch": "main"
         });
 
-        // If file exists, add the SHA to the payload for updating
-        if existing_response.status().is_success() {
-            if let Ok(existing_content) = existing_response.json::<serde_json::Value>().await {
-                if let Some(sha) = existing_content.get("sha").and_then(|s| s.as_str()) {
-                    payload["sha"] = serde_json::Value::String(sha.to_string());
-                    payload["message"] = serde_json::Value::String(format!("Update text: {}", filename));
-                    info!("File exists, updating with SHA: {}", sha);
+        // If file exists, add the SHA to the payload for updating, or skip
+        // the upload entirely when the content hasn't changed.
+        if let Some(existing_content) = existing_content {
+            if let Some(sha) = existing_content.get("sha").and_then(|s| s.as_str()) {
+                if sha == local_sha {
+                    info!("'{}' is unchanged (sha {}), skipping upload", filename, sha);
+                    return Ok(format!(
+                        "https://cdn.jsdelivr.net/gh/{}/{}@main/texts/{}",
+                        self.owner, self.repo, filename
+                    ));
                 }
+
+                payload["sha"] = serde_json::Value::String(sha.to_string());
+                payload["message"] = serde_json::Value::String(format!("Update text: {}", filename));
+                info!("File exists, updating with SHA: {}", sha);
             }
         } else {
             info!("File doesn't exist, creating new file");
         }
 
+        self.put_contents_with_retry(&url, &payload).await?;
+
+        Ok(format!(
+            "https://cdn.jsdelivr.net/gh/{}/{}@main/texts/{}",
+            self.owner, self.repo, filename
+        ))
+    }
+
+    async fn exists(&self, filename: &str) -> Result<bool, ApiError> {
+        Ok(self.get_contents(filename).await?.is_some())
+    }
+
+    async fn fetch(&self, filename: &str) -> Result<String, ApiError> {
+        let url = format!(
+            "https://cdn.jsdelivr.net/gh/{}/{}@main/texts/{}",
+            self.owner, self.repo, filename
+        );
+
         let response = self.client
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
-            .header("Accept", "application/vnd.github.v3+json")
+            .get(&url)
             .header("User-Agent", "OortML")
-            .json(&payload)
             .send()
             .await?;
-        
-        info!("GitHub upload response status: {}", response.status());
-        if response.status().is_success() {
-            Ok(format!(
-                "https://cdn.jsdelivr.net/gh/{}/{}@main/texts/{}",
-                self.owner, self.repo, filename
-            ))
-        } else {
-            let error_body = response.text().await.unwrap_or_else(|_| "Unable to read error".to_string());
-            info!("GitHub upload failed with body: {}", error_body);
-            Err(ApiError::InternalError(format!("GitHub upload failed: {}", error_body)))
+
+        if !response.status().is_success() {
+            return Err(ApiError::InternalError(format!(
+                "Failed to fetch '{}' from jsDelivr: {}",
+                filename,
+                response.status()
+            )));
         }
+
+        Ok(response.text().await?)
     }
 }
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod github;
+pub mod s3;
+pub mod storage;
+
+pub use storage::{storage_from_env, TextStorage};
@@ -0,0 +1,140 @@
+use crate::data::cdn::storage::TextStorage;
+use crate::error::ApiError;
+use async_trait::async_trait;
+use log::info;
+use s3::bucket::Bucket;
+use s3::creds::Credentials;
+use s3::region::Region;
+
+const PRESIGNED_URL_TTL_SECS: u32 = 7 * 24 * 60 * 60;
+
+/// `TextStorage` backed by an S3-compatible object store (AWS S3 or a
+/// self-hosted MinIO), selected via `STORAGE_BACKEND=s3`/`minio`. Objects
+/// are stored under a configurable bucket/prefix; a public bucket returns
+/// a plain object URL while a private one returns a presigned GET URL.
+pub struct S3Storage {
+    bucket: Bucket,
+    prefix: String,
+    public: bool,
+}
+
+impl S3Storage {
+    pub fn new(
+        bucket_name: &str,
+        region: Region,
+        credentials: Credentials,
+        prefix: &str,
+        public: bool,
+    ) -> Result<Self, ApiError> {
+        let bucket = Bucket::new(bucket_name, region, credentials)
+            .map_err(|e| ApiError::InternalError(format!("Invalid S3 bucket config: {}", e)))?
+            .with_path_style();
+
+        Ok(Self {
+            bucket,
+            prefix: prefix.trim_matches('/').to_string(),
+            public,
+        })
+    }
+
+    /// Builds an `S3Storage` from `S3_*`/`MINIO_*` environment variables, so
+    /// operators can point Oort at AWS S3 or a self-hosted MinIO instance
+    /// without a code change:
+    /// - `S3_BUCKET` (required)
+    /// - `S3_ENDPOINT` (custom endpoint, e.g. MinIO's `http://minio:9000`)
+    /// - `S3_REGION` (default `us-east-1`)
+    /// - `S3_ACCESS_KEY` / `S3_SECRET_KEY` (falls back to the default AWS
+    ///   credential chain when unset)
+    /// - `S3_PREFIX` (default empty)
+    /// - `S3_PUBLIC_BUCKET` (`true`/`false`, default `false`)
+    pub fn from_env() -> Self {
+        let bucket_name = std::env::var("S3_BUCKET").unwrap_or_default();
+        let prefix = std::env::var("S3_PREFIX").unwrap_or_default();
+        let public = std::env::var("S3_PUBLIC_BUCKET")
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        let region = match std::env::var("S3_ENDPOINT") {
+            Ok(endpoint) => Region::Custom {
+                region: std::env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                endpoint,
+            },
+            Err(_) => std::env::var("S3_REGION")
+                .ok()
+                .and_then(|r| r.parse().ok())
+                .unwrap_or(Region::UsEast1),
+        };
+
+        let credentials = Credentials::new(
+            std::env::var("S3_ACCESS_KEY").ok().as_deref(),
+            std::env::var("S3_SECRET_KEY").ok().as_deref(),
+            None,
+            None,
+            None,
+        )
+        .unwrap_or(Credentials {
+            access_key: None,
+            secret_key: None,
+            security_token: None,
+            session_token: None,
+            expiration: None,
+        });
+
+        Self::new(&bucket_name, region, credentials, &prefix, public)
+            .expect("Failed to configure S3 storage backend")
+    }
+
+    fn object_key(&self, filename: &str) -> String {
+        if self.prefix.is_empty() {
+            filename.to_string()
+        } else {
+            format!("{}/{}", self.prefix, filename)
+        }
+    }
+}
+
+#[async_trait]
+impl TextStorage for S3Storage {
+    async fn upload_text(&self, content: &str, filename: &str) -> Result<String, ApiError> {
+        let key = self.object_key(filename);
+        info!("Uploading '{}' to S3 bucket '{}'", key, self.bucket.name);
+
+        self.bucket
+            .put_object_with_content_type(&key, content.as_bytes(), "text/plain")
+            .await
+            .map_err(|e| ApiError::InternalError(format!("S3 upload failed: {}", e)))?;
+
+        if self.public {
+            Ok(self.bucket.url() + "/" + &key)
+        } else {
+            self.bucket
+                .presign_get(&key, PRESIGNED_URL_TTL_SECS, None)
+                .await
+                .map_err(|e| ApiError::InternalError(format!("S3 presign failed: {}", e)))
+        }
+    }
+
+    async fn exists(&self, filename: &str) -> Result<bool, ApiError> {
+        let key = self.object_key(filename);
+        let (_, status) = self
+            .bucket
+            .head_object(&key)
+            .await
+            .map_err(|e| ApiError::InternalError(format!("S3 head_object failed: {}", e)))?;
+
+        Ok(status == 200)
+    }
+
+    async fn fetch(&self, filename: &str) -> Result<String, ApiError> {
+        let key = self.object_key(filename);
+        let response = self
+            .bucket
+            .get_object(&key)
+            .await
+            .map_err(|e| ApiError::InternalError(format!("S3 download failed: {}", e)))?;
+
+        String::from_utf8(response.to_vec())
+            .map_err(|e| ApiError::InternalError(format!("S3 object was not valid UTF-8: {}", e)))
+    }
+}
+
@@ -0,0 +1,32 @@
+use crate::error::ApiError;
+use async_trait::async_trait;
+
+/// A place extracted article/text content can be persisted to and served
+/// back from. `GitHubCDN` and `S3Storage` are the two implementors;
+/// `storage_from_env` picks one based on configuration so callers never
+/// need to know which backend is active.
+#[async_trait]
+pub trait TextStorage: Send + Sync {
+    /// Uploads `content` under `filename`, returning a URL the content can
+    /// be fetched back from (a jsDelivr URL, a public S3 object URL, or a
+    /// presigned URL, depending on the backend).
+    async fn upload_text(&self, content: &str, filename: &str) -> Result<String, ApiError>;
+
+    /// Whether `filename` already exists in this backend.
+    async fn exists(&self, filename: &str) -> Result<bool, ApiError>;
+
+    /// Fetches the raw content previously stored under `filename`.
+    async fn fetch(&self, filename: &str) -> Result<String, ApiError>;
+}
+
+/// Selects a `TextStorage` backend from the `STORAGE_BACKEND` environment
+/// variable (`github`, `s3`, or `minio`; defaults to `github` when unset),
+/// so operators can switch backends without a code change.
+pub fn storage_from_env() -> Box<dyn TextStorage> {
+    let backend = std::env::var("STORAGE_BACKEND").unwrap_or_else(|_| "github".to_string());
+
+    match backend.to_lowercase().as_str() {
+        "s3" | "minio" => Box::new(super::s3::S3Storage::from_env()),
+        _ => Box::new(super::github::GitHubCDN::new()),
+    }
+}
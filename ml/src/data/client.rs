@@ -1,35 +1,227 @@
 use crate::concepts::Concept;
+use crate::data::repo::ConceptRepo;
+use crate::embeddings::calibration::SimilarityCalibration;
 use crate::embeddings::Embedding;
 use crate::error::ApiError;
+use crate::retry::{backoff_delay, classify_db_error, RetryDecision, DEFAULT_MAX_ATTEMPTS};
+use crate::search;
+use async_trait::async_trait;
+use cdrs_tokio::authenticators::StaticPasswordAuthenticatorProvider;
 use cdrs_tokio::cluster::session::SessionBuilder;
 use cdrs_tokio::cluster::session::{Session, TcpSessionBuilder};
 use cdrs_tokio::cluster::{NodeTcpConfigBuilder, TcpConnectionManager};
+use cdrs_tokio::consistency::Consistency;
 use cdrs_tokio::load_balancing::RoundRobinLoadBalancingStrategy;
+use cdrs_tokio::query::{BatchQueryBuilder, BatchType};
 use cdrs_tokio::query_values;
 use cdrs_tokio::transport::TransportTcp;
 use cdrs_tokio::types::IntoRustByName;
 use chrono::Utc;
-use futures::future::join_all;
-use log::{error, info};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use log::{error, info, warn};
 use ndarray::{Array1, ArrayBase, Dim, OwnedRepr};
+use sha1::{Digest, Sha1};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::io::Cursor;
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Encodes `embedding` in the canonical on-disk format stored in the
+/// `embedding_vector` blob column: a little-endian `u32` length prefix
+/// (component count) followed by that many little-endian `f32`s. Paired
+/// exactly with [`decode_embedding`] so `save_concept` and
+/// `get_user_concepts` can never drift apart on format.
+pub fn encode_embedding(embedding: &Embedding) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(4 + embedding.len() * 4);
+    bytes
+        .write_u32::<LittleEndian>(embedding.len() as u32)
+        .expect("writing to a Vec<u8> never fails");
+    for &value in embedding.iter() {
+        bytes
+            .write_f32::<LittleEndian>(value)
+            .expect("writing to a Vec<u8> never fails");
+    }
+    bytes
+}
+
+/// Decodes the canonical `embedding_vector` blob format written by
+/// [`encode_embedding`]. Validates the declared length against the actual
+/// byte count rather than silently truncating or padding, so a malformed
+/// column surfaces as an error instead of a wrong-length embedding.
+pub fn decode_embedding(bytes: &[u8]) -> Result<Embedding, ApiError> {
+    if bytes.len() < 4 {
+        return Err(ApiError::InternalError(format!(
+            "embedding_vector blob too short to contain a length prefix: {} bytes",
+            bytes.len()
+        )));
+    }
+
+    let mut cursor = Cursor::new(bytes);
+    let declared_len = cursor
+        .read_u32::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read embedding length prefix: {}", e)))?
+        as usize;
+
+    let expected_bytes = 4 + declared_len * 4;
+    if bytes.len() != expected_bytes {
+        return Err(ApiError::InternalError(format!(
+            "embedding_vector blob has {} bytes but its length prefix ({}) implies {}",
+            bytes.len(),
+            declared_len,
+            expected_bytes
+        )));
+    }
+
+    let mut values = Vec::with_capacity(declared_len);
+    for _ in 0..declared_len {
+        let value = cursor.read_f32::<LittleEndian>().map_err(|e| {
+            ApiError::InternalError(format!("Failed to read embedding component: {}", e))
+        })?;
+        values.push(value);
+    }
+
+    Ok(Array1::from(values))
+}
+
 type CurrentSession = Session<
     TransportTcp,
     TcpConnectionManager,
     RoundRobinLoadBalancingStrategy<TransportTcp, TcpConnectionManager>,
 >;
 
+/// Storage fidelity for embeddings persisted by `save_concept`. `Int8`
+/// shrinks each vector to roughly a quarter of its `None` size (one byte per
+/// component plus two `f32` columns for the shared `min`/`scale`), at the
+/// cost of up to `scale / 2` absolute error per component. In practice this
+/// barely perturbs cosine-similarity rankings, since every stored embedding
+/// is quantized the same way and rank order depends on relative rather than
+/// absolute distances — but callers needing exact embeddings back (e.g.
+/// re-deriving an analogy target) should stick with `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Quantization {
+    #[default]
+    None,
+    Int8,
+}
+
+/// Quantizes `embedding` to `u8` codes: `scale = (max - min) / 255`, each
+/// component stored as `round((x - min) / scale)`. Returns `(min, scale,
+/// codes)`. When every component is equal, `scale` is `0` and every code is
+/// `0`, which `dequantize_int8` reconstructs back to `min` exactly.
+fn quantize_int8(embedding: &Embedding) -> (f32, f32, Vec<u8>) {
+    let min = embedding.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = embedding.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let scale = (max - min) / 255.0;
+
+    let codes = if scale == 0.0 {
+        vec![0u8; embedding.len()]
+    } else {
+        embedding
+            .iter()
+            .map(|&x| (((x - min) / scale).round().clamp(0.0, 255.0)) as u8)
+            .collect()
+    };
+
+    (min, scale, codes)
+}
+
+/// Reconstructs an approximate `Embedding` from `quantize_int8`'s output:
+/// `x ≈ min + code * scale`.
+fn dequantize_int8(min: f32, scale: f32, codes: &[u8]) -> Embedding {
+    Array1::from(
+        codes
+            .iter()
+            .map(|&code| min + code as f32 * scale)
+            .collect::<Vec<f32>>(),
+    )
+}
+
 pub struct DatabaseClient {
     session: CurrentSession,
+    quantization: Quantization,
+    max_retry_attempts: u32,
+    /// Consistency level applied to batched writes (see
+    /// `save_concepts_batch`); `None` leaves the driver default in place.
+    default_consistency: Option<Consistency>,
+}
+
+/// A concept paired with its similarity score, ordered by score so it can
+/// sit in a `BinaryHeap` used as a bounded min-heap (via `Reverse`) in
+/// `search_similar_concepts`.
+#[derive(Debug, Clone, PartialEq)]
+struct ScoredConcept {
+    similarity: f32,
+    concept: Concept,
+}
+
+impl Eq for ScoredConcept {}
+
+impl PartialOrd for ScoredConcept {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredConcept {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.similarity
+            .partial_cmp(&other.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+/// Upper bound on concepts per CQL batch submitted by
+/// `save_concepts_batch`; each concept adds two INSERT statements, so a
+/// batch holds at most twice this many statements. Keeps a single batch
+/// from overloading the coordinator node, which has to hold every statement in the batch in
+/// memory until it's applied.
+const BATCH_CHUNK_SIZE: usize = 50;
+
+/// Outcome of a `save_concepts_batch` call. Unlike a single `Ok(())`, this
+/// lets a caller tell a fully successful save from a partial one and know
+/// which concepts need to be retried.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchSaveSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub failed_concepts: Vec<String>,
 }
 
 impl DatabaseClient {
-    pub async fn new(nodes: &[&str]) -> Result<Self, ApiError> {
-        let node = nodes[0]; // For simplicity, use first node
+    /// Connects to a Cassandra cluster using every contact point in `nodes`,
+    /// so the `RoundRobinLoadBalancingStrategy` configured below actually
+    /// balances across the cluster (and fails over) instead of pinning to
+    /// `nodes[0]`. `auth` is `(username, password)` for a cluster with
+    /// password authentication enabled; `keyspace`, if given, is selected
+    /// with a `USE` statement right after connecting; `consistency`
+    /// overrides the default consistency level used for batched writes
+    /// (see [`save_concepts_batch`](Self::save_concepts_batch)).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        nodes: &[&str],
+        auth: Option<(&str, &str)>,
+        keyspace: Option<&str>,
+        consistency: Option<Consistency>,
+    ) -> Result<Self, ApiError> {
+        if nodes.is_empty() {
+            return Err(ApiError::InternalError(
+                "DatabaseClient::new requires at least one contact point".to_string(),
+            ));
+        }
 
-        let config = NodeTcpConfigBuilder::new()
-            .with_contact_point(cdrs_tokio::cluster::NodeAddress::Hostname(node.to_string()))
+        let mut config_builder = NodeTcpConfigBuilder::new();
+        for node in nodes {
+            config_builder = config_builder
+                .with_contact_point(cdrs_tokio::cluster::NodeAddress::Hostname(node.to_string()));
+        }
+        if let Some((username, password)) = auth {
+            config_builder = config_builder.with_authentication_provider(Arc::new(
+                StaticPasswordAuthenticatorProvider::new(username.to_string(), password.to_string()),
+            ));
+        }
+
+        let config = config_builder
             .build()
             .await
             .map_err(|e| ApiError::InternalError(format!("DB connection error: {}", e)))?;
@@ -39,27 +231,95 @@ impl DatabaseClient {
             .await
             .map_err(|e| ApiError::InternalError(format!("Session build error: {}", e)))?;
 
-        Ok(Self { session })
+        if let Some(keyspace) = keyspace {
+            session
+                .query(format!("USE {}", keyspace))
+                .await
+                .map_err(|e| ApiError::InternalError(format!("Failed to select keyspace: {}", e)))?;
+        }
+
+        Ok(Self {
+            session,
+            quantization: Quantization::default(),
+            max_retry_attempts: DEFAULT_MAX_ATTEMPTS,
+            default_consistency: consistency,
+        })
+    }
+
+    /// Opts into a storage fidelity for embeddings persisted by
+    /// `save_concept`. See [`Quantization`] for the size/recall tradeoff.
+    pub fn with_quantization(mut self, quantization: Quantization) -> Self {
+        self.quantization = quantization;
+        self
+    }
+
+    /// Overrides how many attempts `retry` makes before giving up on a
+    /// transient error. Defaults to [`DEFAULT_MAX_ATTEMPTS`].
+    pub fn with_max_retry_attempts(mut self, max_retry_attempts: u32) -> Self {
+        self.max_retry_attempts = max_retry_attempts;
+        self
+    }
+
+    /// Runs `op`, retrying on transient Cassandra errors (timeouts, an
+    /// overloaded coordinator) with the same exponential-backoff strategy
+    /// used for the embedding model's HTTP calls (see `crate::retry`).
+    /// Gives up immediately on a non-transient error, or after
+    /// `self.max_retry_attempts`, surfacing the final error either way.
+    async fn retry<F, Fut, T>(&self, mut op: F) -> Result<T, ApiError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, ApiError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    let decision = classify_db_error(&err.to_string());
+                    if decision == RetryDecision::GiveUp || attempt >= self.max_retry_attempts {
+                        return Err(err);
+                    }
+
+                    warn!(
+                        "DB operation failed ({:?}) on attempt {}/{}: {}",
+                        decision, attempt, self.max_retry_attempts, err
+                    );
+                    tokio::time::sleep(backoff_delay(attempt, decision)).await;
+                }
+            }
+        }
     }
 
+    /// Loads the concepts a user has saved with a specific embedding
+    /// model/provider. Scoping by `model_id` keeps embeddings from
+    /// different models from ever being compared together (e.g. in
+    /// `cluster_concepts` or similarity search).
     pub async fn get_user_concepts(
         &self,
         user_id: &str,
+        model_id: &str,
     ) -> Result<Vec<(Concept, Embedding)>, ApiError> {
-        let query = "SELECT concept_id, concept_text, embedding_vector FROM store.user_concepts WHERE user_id = ?";
+        let query = "SELECT concept_id, concept_text, embedding_vector, embedding_quantization, embedding_min, embedding_scale \
+                    FROM store.user_concepts WHERE user_id = ? AND embedding_model = ? ALLOW FILTERING";
 
         let uuid = Uuid::parse_str(user_id)
             .map_err(|e| ApiError::InternalError(format!("Invalid UUID: {}", e)))?;
 
         let rows = self
-            .session
-            .query_with_values(query, query_values!(uuid))
-            .await
-            .map_err(|e| ApiError::InternalError(format!("Query error: {}", e)))?
-            .response_body()
-            .map_err(|e| ApiError::InternalError(format!("Response error: {}", e)))?
-            .into_rows()
-            .unwrap_or_default();
+            .retry(|| async {
+                let rows = self
+                    .session
+                    .query_with_values(query, query_values!(uuid, model_id.to_string()))
+                    .await
+                    .map_err(|e| ApiError::InternalError(format!("Query error: {}", e)))?
+                    .response_body()
+                    .map_err(|e| ApiError::InternalError(format!("Response error: {}", e)))?
+                    .into_rows()
+                    .unwrap_or_default();
+                Ok(rows)
+            })
+            .await?;
 
         let mut results = Vec::new();
 
@@ -69,64 +329,35 @@ impl DatabaseClient {
                 ApiError::InternalError(format!("Concept text extraction error: {}", e))
             })?;
 
-            // For the embedding vector, we need to use a different strategy
-            // Let's try to deserialize it manually using the serde functionality
-
-            // First, get the raw bytes from the column - using CQL binary protocol
-            let result: Result<String, _> = row.get_r_by_name("embedding_vector");
+            // `embedding_vector` holds either the canonical length-prefixed
+            // f32 blob (`encode_embedding`) or raw `Int8` codes, per
+            // `embedding_quantization`; a malformed column is a real error,
+            // not something to paper over with an empty embedding.
+            let embedding_bytes: Vec<u8> = row.get_r_by_name("embedding_vector").map_err(|e| {
+                ApiError::InternalError(format!("Embedding vector extraction error: {}", e))
+            })?;
+            let quantization: String = row.get_r_by_name("embedding_quantization").map_err(|e| {
+                ApiError::InternalError(format!("Embedding quantization extraction error: {}", e))
+            })?;
 
-            // If this works, try to parse it as a comma-separated string of floats
-            let embedding_vec: Vec<f32> = match result {
-                Ok(string_vec) => {
-                    // Parse as comma-separated values
-                    string_vec
-                        .split(',')
-                        .filter_map(|s| s.trim().parse::<f32>().ok())
-                        .collect()
-                }
-                Err(_) => {
-                    // If string doesn't work, try to parse it as a JSON array
-                    let result: Result<String, _> = row.get_r_by_name("embedding_vector");
-                    match result {
-                        Ok(json_str) => {
-                            // Parse JSON array
-                            serde_json::from_str::<Vec<f32>>(&json_str).map_err(|e| {
-                                ApiError::InternalError(format!("JSON parsing error: {}", e))
-                            })?
-                        }
-                        Err(_) => {
-                            // Last resort - let's try an alternative approach
-                            // Query the column separately with a different approach
-                            let single_query = "SELECT embedding_vector FROM store.user_concepts WHERE user_id = ? AND concept_id = ?";
-                            let concept_id: Uuid =
-                                row.get_r_by_name("concept_id").map_err(|e| {
-                                    ApiError::InternalError(format!(
-                                        "Concept ID extraction error: {}",
-                                        e
-                                    ))
-                                })?;
-
-                            // Re-query to get the vector in a raw format
-                            let raw_result = self
-                                .session
-                                .query_with_values(single_query, query_values!(uuid, concept_id))
-                                .await
-                                .map_err(|e| {
-                                    ApiError::InternalError(format!("Second query error: {}", e))
-                                })?;
-
-                            // Process to extract the vector based on your actual storage format
-                            // This is a placeholder - you'll need to adapt this to how your data is actually stored
-                            Vec::new()
-                        }
-                    }
+            let embedding: ArrayBase<OwnedRepr<f32>, Dim<[usize; 1]>> = match quantization.as_str()
+            {
+                "int8" => {
+                    let min: f32 = row.get_r_by_name("embedding_min").map_err(|e| {
+                        ApiError::InternalError(format!("Embedding min extraction error: {}", e))
+                    })?;
+                    let scale: f32 = row.get_r_by_name("embedding_scale").map_err(|e| {
+                        ApiError::InternalError(format!("Embedding scale extraction error: {}", e))
+                    })?;
+                    dequantize_int8(min, scale, &embedding_bytes)
                 }
+                _ => decode_embedding(&embedding_bytes)?,
             };
 
             let concept = Concept {
                 concept: concept_text,
+                source_range: None,
             };
-            let embedding: ArrayBase<OwnedRepr<f32>, Dim<[usize; 1]>> = Array1::from(embedding_vec);
 
             results.push((concept, embedding));
         }
@@ -135,91 +366,449 @@ impl DatabaseClient {
         Ok(results)
     }
 
+    /// Deterministic content hash for a concept under a specific embedding
+    /// model. Used as a cache key so identical concept text is never
+    /// re-embedded by the same model.
+    pub fn content_hash(concept: &str, model_id: &str) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(model_id.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(concept.as_bytes());
+        hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Builds a content-hash -> embedding cache from a user's already
+    /// stored concepts (as returned by `get_user_concepts`), so a caller can
+    /// look up whether a freshly extracted concept was already embedded by
+    /// `model_id` before hitting the embedding model again.
+    pub fn embeddings_by_hash(
+        existing: &[(Concept, Embedding)],
+        model_id: &str,
+    ) -> HashMap<String, Embedding> {
+        existing
+            .iter()
+            .map(|(concept, embedding)| {
+                (Self::content_hash(&concept.concept, model_id), embedding.clone())
+            })
+            .collect()
+    }
+
+    /// Ranks a user's stored concepts against `query_text`/`query_embedding`
+    /// (the latter expected to already be unit-normalized) using hybrid
+    /// keyword + semantic search, and returns the top `top_k` by fused
+    /// score. Every embedding is normalized before it's persisted in
+    /// `save_concept`, so the semantic half of the fusion reduces to a
+    /// plain dot product per candidate rather than a full cosine
+    /// computation. See [`search::hybrid_search`] for the fusion details.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_user_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query_text: &str,
+        query_embedding: &Embedding,
+        top_k: usize,
+        semantic_ratio: f32,
+        calibration: Option<&SimilarityCalibration>,
+    ) -> Result<Vec<search::HybridSearchResult>, ApiError> {
+        let candidates = self.get_user_concepts(user_id, model_id).await?;
+        Ok(search::hybrid_search(
+            query_text,
+            query_embedding,
+            &candidates,
+            top_k,
+            semantic_ratio,
+            calibration,
+        ))
+    }
+
+    /// Ranks a user's stored concepts against `query` by cosine similarity
+    /// and returns the top `k`, descending. Every candidate and the query
+    /// are L2-normalized to unit vectors once, reducing cosine similarity
+    /// to a plain dot product; a bounded min-heap of size `k` is kept
+    /// throughout so memory stays `O(k)` regardless of how many concepts
+    /// the user has stored.
+    pub async fn search_similar_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query: &Embedding,
+        k: usize,
+    ) -> Result<Vec<(Concept, f32)>, ApiError> {
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let candidates = self.get_user_concepts(user_id, model_id).await?;
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let normalized_query = search::normalize(query);
+        let mut heap: BinaryHeap<Reverse<ScoredConcept>> = BinaryHeap::with_capacity(k + 1);
+
+        for (concept, embedding) in candidates {
+            if embedding.len() != normalized_query.len() {
+                return Err(ApiError::DimensionalityError(format!(
+                    "Stored embedding dimension {} does not match query dimension {}",
+                    embedding.len(),
+                    normalized_query.len()
+                )));
+            }
+
+            // A zero-norm embedding is left unchanged by `normalize`, so its
+            // dot product against the (non-zero) query naturally lands at
+            // (near) 0 rather than needing a separate guard.
+            let normalized_embedding = search::normalize(&embedding);
+            let similarity = normalized_embedding.dot(&normalized_query);
+
+            heap.push(Reverse(ScoredConcept { similarity, concept }));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        let mut results: Vec<(Concept, f32)> = heap
+            .into_iter()
+            .map(|Reverse(scored)| (scored.concept, scored.similarity))
+            .collect();
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(results)
+    }
+
     pub async fn save_concept(
         &self,
         user_id: &str,
         concept: &Concept,
         embedding: &Embedding,
+        model_id: &str,
     ) -> Result<(), ApiError> {
         let concept_id = Uuid::new_v4();
         let user_uuid = Uuid::parse_str(user_id)
             .map_err(|e| ApiError::InternalError(format!("Invalid UUID: {}", e)))?;
         let now = Utc::now();
 
-        // Convert embedding to Vec<f64> for Cassandra compatibility
-        let embedding_vec: Vec<f64> = embedding.iter().map(|&x| x as f64).collect();
+        // Normalize to a unit vector before persisting so every stored
+        // embedding supports plain dot-product ranking in
+        // `search_user_concepts`, with no per-query renormalization.
+        let normalized = search::normalize(embedding);
+
+        // Encode per `self.quantization`: the canonical blob format (see
+        // `encode_embedding`), which round-trips through `decode_embedding`
+        // at full f32 precision, or `Int8` codes alongside their shared
+        // `min`/`scale` for a ~4x smaller row.
+        let (embedding_bytes, quantization_label, embedding_min, embedding_scale) =
+            match self.quantization {
+                Quantization::None => (encode_embedding(&normalized), "none", 0.0_f32, 0.0_f32),
+                Quantization::Int8 => {
+                    let (min, scale, codes) = quantize_int8(&normalized);
+                    (codes, "int8", min, scale)
+                }
+            };
 
         // Insert into user_concepts table
         let query = "INSERT INTO store.user_concepts \
-                    (user_id, concept_id, concept_text, embedding_vector, created_at) \
-                    VALUES (?, ?, ?, ?, ?)";
-
-        self.session
-            .query_with_values(
-                query,
-                query_values!(
-                    user_uuid,
-                    concept_id,
-                    concept.concept.clone(),
-                    embedding_vec,
-                    now
-                ),
-            )
-            .await
-            .map_err(|e| ApiError::InternalError(format!("Save concept error: {}", e)))?;
+                    (user_id, concept_id, concept_text, embedding_vector, embedding_quantization, \
+                     embedding_min, embedding_scale, embedding_model, created_at) \
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)";
+
+        self.retry(|| async {
+            self.session
+                .query_with_values(
+                    query,
+                    query_values!(
+                        user_uuid,
+                        concept_id,
+                        concept.concept.clone(),
+                        embedding_bytes.clone(),
+                        quantization_label.to_string(),
+                        embedding_min,
+                        embedding_scale,
+                        model_id.to_string(),
+                        now
+                    ),
+                )
+                .await
+                .map_err(|e| ApiError::InternalError(format!("Save concept error: {}", e)))
+        })
+        .await?;
 
         // Insert source information
         let source_query = "INSERT INTO store.concept_sources \
                            (concept_id, user_id, source_type, source_text, created_at) \
                            VALUES (?, ?, ?, ?, ?)";
 
-        self.session
-            .query_with_values(
-                source_query,
-                query_values!(
-                    concept_id,
-                    user_uuid,
-                    "text_upload",
-                    "User uploaded text",
-                    now
-                ),
-            )
-            .await
-            .map_err(|e| ApiError::InternalError(format!("Save source error: {}", e)))?;
+        self.retry(|| async {
+            self.session
+                .query_with_values(
+                    source_query,
+                    query_values!(
+                        concept_id,
+                        user_uuid,
+                        "text_upload",
+                        "User uploaded text",
+                        now
+                    ),
+                )
+                .await
+                .map_err(|e| ApiError::InternalError(format!("Save source error: {}", e)))
+        })
+        .await?;
 
         Ok(())
     }
 
+    /// Saves every `(concept, embedding)` pair for `user_id` under
+    /// `model_id` as unlogged CQL batches of at most `BATCH_CHUNK_SIZE`
+    /// statement pairs, using the two INSERT statements prepared once up
+    /// front rather than re-parsed per row. The user UUID is likewise
+    /// parsed once rather than once per `save_concept` call. A chunk that
+    /// fails is recorded in the returned summary instead of being silently
+    /// dropped; earlier/later chunks still get their own attempt.
     pub async fn save_concepts_batch(
         &self,
         user_id: &str,
         concepts: &[Concept],
         embeddings: &[Embedding],
-    ) -> Result<(), ApiError> {
+        model_id: &str,
+    ) -> Result<BatchSaveSummary, ApiError> {
         if concepts.len() != embeddings.len() {
             return Err(ApiError::InternalError(
                 "Concept and embedding count mismatch".to_string(),
             ));
         }
 
-        let mut futures = Vec::new();
+        let user_uuid = Uuid::parse_str(user_id)
+            .map_err(|e| ApiError::InternalError(format!("Invalid UUID: {}", e)))?;
 
-        for (concept, embedding) in concepts.iter().zip(embeddings.iter()) {
-            let future = self.save_concept(user_id, concept, embedding);
-            futures.push(future);
-        }
+        let concept_query = "INSERT INTO store.user_concepts \
+                    (user_id, concept_id, concept_text, embedding_vector, embedding_quantization, \
+                     embedding_min, embedding_scale, embedding_model, created_at) \
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        let source_query = "INSERT INTO store.concept_sources \
+                           (concept_id, user_id, source_type, source_text, created_at) \
+                           VALUES (?, ?, ?, ?, ?)";
 
-        // Execute all futures concurrently
-        let results = join_all(futures).await;
+        let prepared_concept = self.session.prepare(concept_query).await.map_err(|e| {
+            ApiError::InternalError(format!("Failed to prepare concept insert: {}", e))
+        })?;
+        let prepared_source = self.session.prepare(source_query).await.map_err(|e| {
+            ApiError::InternalError(format!("Failed to prepare source insert: {}", e))
+        })?;
 
-        // Check for errors
-        for result in results {
-            if let Err(e) = result {
-                error!("Error saving concept: {}", e);
-                // Continue saving other concepts even if one fails
+        let now = Utc::now();
+        let rows: Vec<(Uuid, &Concept, Vec<u8>, &'static str, f32, f32)> = concepts
+            .iter()
+            .zip(embeddings.iter())
+            .map(|(concept, embedding)| {
+                let normalized = search::normalize(embedding);
+                let (embedding_bytes, quantization_label, embedding_min, embedding_scale) =
+                    match self.quantization {
+                        Quantization::None => {
+                            (encode_embedding(&normalized), "none", 0.0_f32, 0.0_f32)
+                        }
+                        Quantization::Int8 => {
+                            let (min, scale, codes) = quantize_int8(&normalized);
+                            (codes, "int8", min, scale)
+                        }
+                    };
+                (
+                    Uuid::new_v4(),
+                    concept,
+                    embedding_bytes,
+                    quantization_label,
+                    embedding_min,
+                    embedding_scale,
+                )
+            })
+            .collect();
+
+        let mut summary = BatchSaveSummary::default();
+
+        for chunk in rows.chunks(BATCH_CHUNK_SIZE) {
+            let result = self
+                .retry(|| async {
+                    let mut batch = BatchQueryBuilder::new().batch_type(BatchType::Unlogged);
+                    if let Some(consistency) = self.default_consistency {
+                        batch = batch.consistency(consistency);
+                    }
+                    for (concept_id, concept, embedding_bytes, quantization_label, embedding_min, embedding_scale) in chunk {
+                        batch = batch
+                            .add_query_prepared(
+                                &prepared_concept,
+                                query_values!(
+                                    user_uuid,
+                                    *concept_id,
+                                    concept.concept.clone(),
+                                    embedding_bytes.clone(),
+                                    quantization_label.to_string(),
+                                    *embedding_min,
+                                    *embedding_scale,
+                                    model_id.to_string(),
+                                    now
+                                ),
+                            )
+                            .add_query_prepared(
+                                &prepared_source,
+                                query_values!(
+                                    *concept_id,
+                                    user_uuid,
+                                    "text_upload",
+                                    "User uploaded text",
+                                    now
+                                ),
+                            );
+                    }
+
+                    self.session
+                        .batch(batch.build())
+                        .await
+                        .map_err(|e| ApiError::InternalError(format!("Batch save error: {}", e)))
+                })
+                .await;
+
+            match result {
+                Ok(_) => summary.succeeded += chunk.len(),
+                Err(e) => {
+                    error!("Batch of {} concepts failed to save: {}", chunk.len(), e);
+                    summary.failed += chunk.len();
+                    summary
+                        .failed_concepts
+                        .extend(chunk.iter().map(|(_, concept, ..)| concept.concept.clone()));
+                }
             }
         }
 
-        Ok(())
+        Ok(summary)
+    }
+}
+
+#[async_trait]
+impl ConceptRepo for DatabaseClient {
+    async fn get_user_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+    ) -> Result<Vec<(Concept, Embedding)>, ApiError> {
+        DatabaseClient::get_user_concepts(self, user_id, model_id).await
+    }
+
+    async fn save_concept(
+        &self,
+        user_id: &str,
+        concept: &Concept,
+        embedding: &Embedding,
+        model_id: &str,
+    ) -> Result<(), ApiError> {
+        DatabaseClient::save_concept(self, user_id, concept, embedding, model_id).await
+    }
+
+    async fn save_concepts_batch(
+        &self,
+        user_id: &str,
+        concepts: &[Concept],
+        embeddings: &[Embedding],
+        model_id: &str,
+    ) -> Result<BatchSaveSummary, ApiError> {
+        DatabaseClient::save_concepts_batch(self, user_id, concepts, embeddings, model_id).await
+    }
+
+    async fn search_similar_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query: &Embedding,
+        k: usize,
+    ) -> Result<Vec<(Concept, f32)>, ApiError> {
+        DatabaseClient::search_similar_concepts(self, user_id, model_id, query, k).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn search_user_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query_text: &str,
+        query_embedding: &Embedding,
+        top_k: usize,
+        semantic_ratio: f32,
+        calibration: Option<&SimilarityCalibration>,
+    ) -> Result<Vec<search::HybridSearchResult>, ApiError> {
+        DatabaseClient::search_user_concepts(
+            self,
+            user_id,
+            model_id,
+            query_text,
+            query_embedding,
+            top_k,
+            semantic_ratio,
+            calibration,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_embedding_round_trips() {
+        let embedding = Array1::from(vec![0.5_f32, -1.25, 3.0, 0.0]);
+        let bytes = encode_embedding(&embedding);
+        let decoded = decode_embedding(&bytes).unwrap();
+        assert_eq!(decoded.to_vec(), embedding.to_vec());
+    }
+
+    #[test]
+    fn test_encode_decode_empty_embedding() {
+        let embedding = Array1::from(Vec::<f32>::new());
+        let bytes = encode_embedding(&embedding);
+        let decoded = decode_embedding(&bytes).unwrap();
+        assert_eq!(decoded.len(), 0);
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_blob() {
+        assert!(decode_embedding(&[1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_length_prefix_mismatch() {
+        let mut bytes = encode_embedding(&Array1::from(vec![1.0_f32, 2.0, 3.0]));
+        bytes.truncate(bytes.len() - 4); // drop the last component's bytes
+        assert!(decode_embedding(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_quantize_int8_round_trip_error_within_scale() {
+        let embedding = Array1::from(vec![-1.0_f32, -0.3, 0.0, 0.7, 1.0]);
+        let (min, scale, codes) = quantize_int8(&embedding);
+        let reconstructed = dequantize_int8(min, scale, &codes);
+
+        for (original, approx) in embedding.iter().zip(reconstructed.iter()) {
+            assert!(
+                (original - approx).abs() <= scale,
+                "reconstruction error {} exceeded scale {}",
+                (original - approx).abs(),
+                scale
+            );
+        }
+    }
+
+    #[test]
+    fn test_quantize_int8_constant_vector_has_zero_scale() {
+        let embedding = Array1::from(vec![2.0_f32; 4]);
+        let (min, scale, codes) = quantize_int8(&embedding);
+        assert_eq!(scale, 0.0);
+        let reconstructed = dequantize_int8(min, scale, &codes);
+        assert_eq!(reconstructed.to_vec(), embedding.to_vec());
+    }
+
+    #[test]
+    fn test_quantize_int8_codes_span_full_byte_range() {
+        let embedding = Array1::from(vec![-2.0_f32, 2.0]);
+        let (_, _, codes) = quantize_int8(&embedding);
+        assert_eq!(codes, vec![0, 255]);
     }
 }
@@ -0,0 +1,7 @@
+pub mod cdn;
+pub mod client;
+pub mod repo;
+pub mod scrape_cache;
+pub mod scraper;
+
+pub use repo::{ConceptRepo, InMemoryConceptRepo};
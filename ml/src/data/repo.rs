@@ -0,0 +1,239 @@
+use crate::concepts::Concept;
+use crate::data::client::BatchSaveSummary;
+use crate::embeddings::calibration::SimilarityCalibration;
+use crate::embeddings::Embedding;
+use crate::error::ApiError;
+use crate::search;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+/// Storage for a user's extracted concepts and their embeddings, independent
+/// of the backend that persists them. `DatabaseClient` (`super::client`) is
+/// the production implementation, backed by Cassandra; `InMemoryConceptRepo`
+/// below is a lightweight implementation for tests and local runs that need
+/// no live cluster. Handlers depend on `Arc<dyn ConceptRepo>` so the backend
+/// can be swapped without touching call sites.
+#[async_trait]
+pub trait ConceptRepo: Send + Sync {
+    /// Loads the concepts a user has saved with a specific embedding
+    /// model/provider.
+    async fn get_user_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+    ) -> Result<Vec<(Concept, Embedding)>, ApiError>;
+
+    /// Persists a single concept and its embedding for `user_id` under
+    /// `model_id`.
+    async fn save_concept(
+        &self,
+        user_id: &str,
+        concept: &Concept,
+        embedding: &Embedding,
+        model_id: &str,
+    ) -> Result<(), ApiError>;
+
+    /// Persists every `(concept, embedding)` pair for `user_id` under
+    /// `model_id`, returning a summary of how many succeeded rather than
+    /// silently dropping per-item failures.
+    async fn save_concepts_batch(
+        &self,
+        user_id: &str,
+        concepts: &[Concept],
+        embeddings: &[Embedding],
+        model_id: &str,
+    ) -> Result<BatchSaveSummary, ApiError>;
+
+    /// Ranks a user's stored concepts against `query` by cosine similarity
+    /// and returns the top `k`, descending. Used by the `/api/search` route
+    /// when the caller sets `semantic_only`, to skip keyword extraction and
+    /// RRF fusion entirely; `search_user_concepts` is the route's default.
+    async fn search_similar_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query: &Embedding,
+        k: usize,
+    ) -> Result<Vec<(Concept, f32)>, ApiError>;
+
+    /// Ranks a user's stored concepts against `query_text`/`query_embedding`
+    /// using hybrid keyword + semantic search, and returns the top `top_k`
+    /// by fused score. See [`search::hybrid_search`] for the fusion details.
+    #[allow(clippy::too_many_arguments)]
+    async fn search_user_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query_text: &str,
+        query_embedding: &Embedding,
+        top_k: usize,
+        semantic_ratio: f32,
+        calibration: Option<&SimilarityCalibration>,
+    ) -> Result<Vec<search::HybridSearchResult>, ApiError>;
+}
+
+/// Key a user's concepts are grouped under: concepts from different
+/// embedding models are never mixed together, mirroring the
+/// `embedding_model` scoping `DatabaseClient` enforces via its CQL schema.
+type RepoKey = (String, String);
+
+/// In-memory `ConceptRepo`, for unit tests and local runs that shouldn't
+/// need a live Cassandra cluster. Contents are lost on restart.
+#[derive(Default)]
+pub struct InMemoryConceptRepo {
+    concepts: Mutex<HashMap<RepoKey, Vec<(Concept, Embedding)>>>,
+}
+
+impl InMemoryConceptRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ConceptRepo for InMemoryConceptRepo {
+    async fn get_user_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+    ) -> Result<Vec<(Concept, Embedding)>, ApiError> {
+        let concepts = self.concepts.lock().await;
+        Ok(concepts
+            .get(&(user_id.to_string(), model_id.to_string()))
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn save_concept(
+        &self,
+        user_id: &str,
+        concept: &Concept,
+        embedding: &Embedding,
+        model_id: &str,
+    ) -> Result<(), ApiError> {
+        let mut concepts = self.concepts.lock().await;
+        concepts
+            .entry((user_id.to_string(), model_id.to_string()))
+            .or_default()
+            .push((concept.clone(), search::normalize(embedding)));
+        Ok(())
+    }
+
+    async fn save_concepts_batch(
+        &self,
+        user_id: &str,
+        concepts: &[Concept],
+        embeddings: &[Embedding],
+        model_id: &str,
+    ) -> Result<BatchSaveSummary, ApiError> {
+        if concepts.len() != embeddings.len() {
+            return Err(ApiError::InternalError(
+                "Concept and embedding count mismatch".to_string(),
+            ));
+        }
+
+        let mut summary = BatchSaveSummary::default();
+        for (concept, embedding) in concepts.iter().zip(embeddings.iter()) {
+            match self.save_concept(user_id, concept, embedding, model_id).await {
+                Ok(()) => summary.succeeded += 1,
+                Err(_) => {
+                    summary.failed += 1;
+                    summary.failed_concepts.push(concept.concept.clone());
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    async fn search_similar_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query: &Embedding,
+        k: usize,
+    ) -> Result<Vec<(Concept, f32)>, ApiError> {
+        let candidates = self.get_user_concepts(user_id, model_id).await?;
+        let normalized_query = search::normalize(query);
+        Ok(search::top_k_similar(&normalized_query, &candidates, k, None))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn search_user_concepts(
+        &self,
+        user_id: &str,
+        model_id: &str,
+        query_text: &str,
+        query_embedding: &Embedding,
+        top_k: usize,
+        semantic_ratio: f32,
+        calibration: Option<&SimilarityCalibration>,
+    ) -> Result<Vec<search::HybridSearchResult>, ApiError> {
+        let candidates = self.get_user_concepts(user_id, model_id).await?;
+        Ok(search::hybrid_search(
+            query_text,
+            query_embedding,
+            &candidates,
+            top_k,
+            semantic_ratio,
+            calibration,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn concept(text: &str) -> Concept {
+        Concept {
+            concept: text.to_string(),
+            source_range: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get_round_trips() {
+        let repo = InMemoryConceptRepo::new();
+        repo.save_concept("user-1", &concept("alpha"), &array![1.0, 0.0], "model-a")
+            .await
+            .unwrap();
+
+        let stored = repo.get_user_concepts("user-1", "model-a").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].0.concept, "alpha");
+    }
+
+    #[tokio::test]
+    async fn test_concepts_scoped_by_model() {
+        let repo = InMemoryConceptRepo::new();
+        repo.save_concept("user-1", &concept("alpha"), &array![1.0, 0.0], "model-a")
+            .await
+            .unwrap();
+
+        let other_model = repo.get_user_concepts("user-1", "model-b").await.unwrap();
+        assert!(other_model.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_similar_concepts_ranks_by_cosine_similarity() {
+        let repo = InMemoryConceptRepo::new();
+        repo.save_concepts_batch(
+            "user-1",
+            &[concept("aligned"), concept("orthogonal")],
+            &[array![1.0, 0.0], array![0.0, 1.0]],
+            "model-a",
+        )
+        .await
+        .unwrap();
+
+        let results = repo
+            .search_similar_concepts("user-1", "model-a", &array![1.0, 0.0], 1)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.concept, "aligned");
+    }
+}
@@ -0,0 +1,72 @@
+use crate::data::scraper::ScrapedArticle;
+use async_trait::async_trait;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+/// Default capacity of `InMemoryScrapeCache` when none is specified.
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+/// A previously scraped article plus the validators needed to revalidate
+/// it (`ETag`/`Last-Modified`) and, if `Cache-Control: max-age` was
+/// present, when it expires. `ArticleScraper` revalidates every cached
+/// entry with a conditional request, and drops an expired one instead of
+/// revalidating it.
+#[derive(Clone)]
+pub struct CachedScrape {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub article: ScrapedArticle,
+    pub expires_at: Option<Instant>,
+}
+
+impl CachedScrape {
+    pub fn is_expired(&self) -> bool {
+        matches!(self.expires_at, Some(expires_at) if Instant::now() >= expires_at)
+    }
+}
+
+/// Caches scraped articles keyed by URL so `ArticleScraper` can send
+/// conditional requests instead of re-downloading and re-parsing unchanged
+/// pages. `InMemoryScrapeCache` is the default; implement this trait again
+/// for a disk-backed store if articles need to survive a restart.
+#[async_trait]
+pub trait ScrapeCache: Send + Sync {
+    async fn get(&self, url: &str) -> Option<CachedScrape>;
+    async fn put(&self, url: &str, entry: CachedScrape);
+}
+
+/// Bounded in-memory LRU `ScrapeCache`. Cheap to share across requests via
+/// `Arc`, but its contents are lost on restart.
+pub struct InMemoryScrapeCache {
+    entries: Mutex<LruCache<String, CachedScrape>>,
+}
+
+impl InMemoryScrapeCache {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(1).unwrap());
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl Default for InMemoryScrapeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+#[async_trait]
+impl ScrapeCache for InMemoryScrapeCache {
+    async fn get(&self, url: &str) -> Option<CachedScrape> {
+        let mut entries = self.entries.lock().await;
+        entries.get(url).cloned()
+    }
+
+    async fn put(&self, url: &str, entry: CachedScrape) {
+        let mut entries = self.entries.lock().await;
+        entries.put(url.to_string(), entry);
+    }
+}
@@ -1,9 +1,24 @@
+use crate::data::scrape_cache::{CachedScrape, InMemoryScrapeCache, ScrapeCache};
 use crate::error::ApiError;
 use dom_query::Document;
 use dom_smoothie::Readability;
+use futures::future::join_all;
 use log::info;
 use regex::Regex;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Semaphore};
+
+/// Default number of URLs `scrape_many` fetches concurrently.
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// Default number of simultaneous requests `scrape_many` allows to the
+/// same host, so one batch doesn't hammer a single site.
+pub const DEFAULT_PER_HOST_CONCURRENCY: usize = 2;
+
+/// Default wall-clock budget for an entire `scrape_many` call.
+pub const DEFAULT_BATCH_DEADLINE: Duration = Duration::from_secs(120);
 
 const NOISE_SELECTORS: &[&str] = &[
     // Reading time
@@ -94,6 +109,7 @@ fn clean_extracted_text(text: &str) -> String {
     result.trim().to_string()
 }
 
+#[derive(Clone)]
 pub struct ScrapedArticle {
     pub title: String,
     pub text_content: String,
@@ -101,6 +117,7 @@ pub struct ScrapedArticle {
 
 pub struct ArticleScraper {
     client: reqwest::Client,
+    cache: Option<Arc<dyn ScrapeCache>>,
 }
 
 impl ArticleScraper {
@@ -112,7 +129,17 @@ impl ArticleScraper {
             .build()
             .expect("Failed to build HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            cache: Some(Arc::new(InMemoryScrapeCache::default())),
+        }
+    }
+
+    /// Overrides the response cache (e.g. with a disk-backed
+    /// `ScrapeCache`), or disables caching entirely with `None`.
+    pub fn with_cache(mut self, cache: Option<Arc<dyn ScrapeCache>>) -> Self {
+        self.cache = cache;
+        self
     }
 
     pub async fn scrape_url(&self, url: &str) -> Result<ScrapedArticle, ApiError> {
@@ -122,12 +149,34 @@ impl ArticleScraper {
             ));
         }
 
+        let cached = match &self.cache {
+            Some(cache) => cache.get(url).await.filter(|entry| !entry.is_expired()),
+            None => None,
+        };
+
         info!("Fetching URL: {}", url);
 
-        let response = self.client.get(url).send().await.map_err(|e| {
+        let mut request = self.client.get(url);
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send().await.map_err(|e| {
             ApiError::UrlFetchError(format!("Failed to fetch URL: {}", e))
         })?;
 
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                info!("'{}' not modified since last scrape, using cached article", url);
+                return Ok(entry.article);
+            }
+        }
+
         let status = response.status();
         if !status.is_success() {
             return Err(ApiError::UrlFetchError(format!(
@@ -136,6 +185,10 @@ impl ArticleScraper {
             )));
         }
 
+        let etag = header_str(&response, reqwest::header::ETAG);
+        let last_modified = header_str(&response, reqwest::header::LAST_MODIFIED);
+        let cache_control = header_str(&response, reqwest::header::CACHE_CONTROL);
+
         let html = response.text().await.map_err(|e| {
             ApiError::UrlFetchError(format!("Failed to read response body: {}", e))
         })?;
@@ -167,11 +220,157 @@ impl ArticleScraper {
             text_content.len()
         );
 
-        Ok(ScrapedArticle {
+        let article = ScrapedArticle {
             title,
             text_content,
-        })
+        };
+
+        if let Some(cache) = &self.cache {
+            if let Some(max_age) = parse_cache_control(cache_control.as_deref()) {
+                cache
+                    .put(
+                        url,
+                        CachedScrape {
+                            etag,
+                            last_modified,
+                            article: article.clone(),
+                            expires_at: max_age.map(|d| Instant::now() + d),
+                        },
+                    )
+                    .await;
+            }
+        }
+
+        Ok(article)
+    }
+
+    /// Scrapes many URLs concurrently, under `DEFAULT_BATCH_CONCURRENCY`
+    /// total in-flight requests, `DEFAULT_PER_HOST_CONCURRENCY` to any one
+    /// host, and an overall `DEFAULT_BATCH_DEADLINE`. One failing or slow
+    /// URL never aborts the rest of the batch; each result lines up
+    /// positionally with its input URL.
+    pub async fn scrape_many(&self, urls: &[String]) -> Vec<Result<ScrapedArticle, ApiError>> {
+        self.scrape_many_with(
+            urls,
+            DEFAULT_BATCH_CONCURRENCY,
+            DEFAULT_PER_HOST_CONCURRENCY,
+            DEFAULT_BATCH_DEADLINE,
+        )
+        .await
     }
+
+    /// Like `scrape_many`, with explicit concurrency and deadline overrides.
+    pub async fn scrape_many_with(
+        &self,
+        urls: &[String],
+        concurrency: usize,
+        per_host_concurrency: usize,
+        deadline: Duration,
+    ) -> Vec<Result<ScrapedArticle, ApiError>> {
+        let global = Arc::new(Semaphore::new(concurrency.max(1)));
+        let host_semaphores: Arc<Mutex<HashMap<String, Arc<Semaphore>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let per_host_concurrency = per_host_concurrency.max(1);
+        let deadline_at = Instant::now() + deadline;
+
+        let tasks = urls.iter().map(|url| {
+            let global = Arc::clone(&global);
+            let host_semaphores = Arc::clone(&host_semaphores);
+            async move {
+                let _global_permit = global
+                    .acquire()
+                    .await
+                    .expect("batch scrape semaphore was closed");
+
+                let host_semaphore = {
+                    let mut hosts = host_semaphores.lock().await;
+                    Arc::clone(
+                        hosts
+                            .entry(host_of(url))
+                            .or_insert_with(|| Arc::new(Semaphore::new(per_host_concurrency))),
+                    )
+                };
+                let _host_permit = host_semaphore
+                    .acquire()
+                    .await
+                    .expect("per-host scrape semaphore was closed");
+
+                self.scrape_with_deadline(url, deadline_at).await
+            }
+        });
+
+        join_all(tasks).await
+    }
+
+    /// Scrapes a single URL, failing fast if `deadline_at` has already
+    /// passed or is reached before the fetch completes.
+    async fn scrape_with_deadline(
+        &self,
+        url: &str,
+        deadline_at: Instant,
+    ) -> Result<ScrapedArticle, ApiError> {
+        let remaining = deadline_at.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(ApiError::UrlFetchError(format!(
+                "Batch deadline exceeded before '{}' could be fetched",
+                url
+            )));
+        }
+
+        match tokio::time::timeout(remaining, self.scrape_url(url)).await {
+            Ok(result) => result,
+            Err(_) => Err(ApiError::UrlFetchError(format!(
+                "Timed out fetching '{}' before the batch deadline",
+                url
+            ))),
+        }
+    }
+}
+
+/// Extracts the host component from a URL for per-host politeness, without
+/// pulling in a full URL-parsing dependency.
+fn host_of(url: &str) -> String {
+    let without_scheme = url.split("://").nth(1).unwrap_or(url);
+    without_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or(without_scheme)
+        .to_string()
+}
+
+fn header_str(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+}
+
+/// Decides whether a response may be cached and for how long, from its
+/// `Cache-Control` header. Returns `None` when caching is disallowed
+/// (`no-store`); otherwise `Some(max_age)`, where `max_age` is `None` when
+/// no `max-age` directive was present (cache indefinitely, relying on
+/// `ETag`/`Last-Modified` revalidation to keep it fresh).
+fn parse_cache_control(cache_control: Option<&str>) -> Option<Option<Duration>> {
+    let directives = match cache_control {
+        Some(directives) => directives,
+        None => return Some(None),
+    };
+
+    if directives
+        .split(',')
+        .any(|d| d.trim().eq_ignore_ascii_case("no-store"))
+    {
+        return None;
+    }
+
+    let max_age = directives.split(',').find_map(|d| {
+        d.trim()
+            .strip_prefix("max-age=")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    });
+
+    Some(max_age.map(Duration::from_secs))
 }
 
 pub fn derive_filename(title: &str, url: &str) -> String {
@@ -309,6 +508,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_host_of() {
+        assert_eq!(host_of("https://example.com/path"), "example.com");
+        assert_eq!(host_of("http://example.com:8080/a/b?q=1"), "example.com:8080");
+    }
+
+    #[test]
+    fn test_parse_cache_control_no_store() {
+        assert_eq!(parse_cache_control(Some("no-store")), None);
+    }
+
+    #[test]
+    fn test_parse_cache_control_max_age() {
+        assert_eq!(
+            parse_cache_control(Some("public, max-age=600")),
+            Some(Some(Duration::from_secs(600)))
+        );
+    }
+
+    #[test]
+    fn test_parse_cache_control_missing_header_caches_indefinitely() {
+        assert_eq!(parse_cache_control(None), Some(None));
+    }
+
+    #[test]
+    fn test_parse_cache_control_no_max_age_directive() {
+        assert_eq!(parse_cache_control(Some("public")), Some(None));
+    }
+
     #[test]
     fn test_pre_clean_dom() {
         let html = r#"<html><body>
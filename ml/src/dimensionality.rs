@@ -1,8 +1,11 @@
-use crate::concepts::Concept;
+use crate::concepts::{fuzzy_duplicate_groups, Concept};
+use crate::embeddings::calibration::{calibrate, SimilarityCalibration};
+use crate::embeddings::quantized::{DistanceTable, ProductQuantizer, QuantizedEmbedding};
 use crate::embeddings::Embedding;
 use crate::error::ApiError;
+use crate::search;
 use log::info;
-use ndarray::{Array2, ArrayView1};
+use ndarray::{Array1, Array2, ArrayView1};
 use ndarray_stats::QuantileExt;
 use ndarray_linalg::Norm;
 use linfa::prelude::*;
@@ -10,16 +13,21 @@ use linfa_clustering::KMeans;
 use linfa_reduction::Pca;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::ops::RangeInclusive;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConceptGroup {
     pub concepts: Vec<String>,
+    /// The group's merged/averaged embedding (full dimensionality, unlike
+    /// `reduced_embedding`), so it can be indexed for `EmbeddingIndex`
+    /// queries alongside raw concepts.
+    pub embedding: Vec<f32>,
     pub reduced_embedding: Vec<f32>,
     pub cluster: usize,
 }
 
 // Helper function to calculate cosine similarity
-fn cosine_similarity(a: ArrayView1<f32>, b: ArrayView1<f32>) -> f32 {
+pub(crate) fn cosine_similarity(a: ArrayView1<f32>, b: ArrayView1<f32>) -> f32 {
     let norm_a = a.norm_l2();
     let norm_b = b.norm_l2();
     
@@ -31,41 +39,72 @@ fn cosine_similarity(a: ArrayView1<f32>, b: ArrayView1<f32>) -> f32 {
     a.dot(&b) / (norm_a * norm_b)
 }
 
-// Merge similar concepts based on cosine similarity
+/// Approximate cosine similarity between a `DistanceTable`'s query and a
+/// `QuantizedEmbedding`, computed entirely from `quantizer`'s precomputed
+/// per-subspace lookups rather than reconstructing either vector. This is
+/// the quantized counterpart to `cosine_similarity` above, so similarity
+/// queries can run directly on product-quantized storage.
+pub(crate) fn cosine_similarity_quantized(
+    quantizer: &ProductQuantizer,
+    table: &DistanceTable,
+    quantized: &QuantizedEmbedding,
+) -> f32 {
+    quantizer.cosine_similarity(table, quantized)
+}
+
+// Merge similar concepts based on cosine similarity, pre-merged by bounded
+// string edit distance
 pub fn merge_similar_concepts(
     concepts: &[Concept],
     embeddings: &[Embedding],
     similarity_threshold: f32,
+    calibration: Option<&SimilarityCalibration>,
+    max_edit_distance: usize,
 ) -> Result<Vec<(Vec<String>, Embedding)>, ApiError> {
     if concepts.is_empty() || embeddings.is_empty() {
         return Err(ApiError::InternalError("Empty concepts or embeddings".to_string()));
     }
-    
+
     if concepts.len() != embeddings.len() {
         return Err(ApiError::InternalError(format!(
             "Concepts length ({}) does not match embeddings length ({})",
             concepts.len(), embeddings.len()
         )));
     }
-    
+
+    // String-level pre-merge: concepts within `max_edit_distance` of each
+    // other (e.g. "color"/"colour", minor typos) are grouped together
+    // regardless of embedding similarity. This composes with, rather than
+    // replaces, the cosine-based merge below.
+    let concept_strings: Vec<String> = concepts.iter().map(|c| c.concept.clone()).collect();
+    let fuzzy_groups = fuzzy_duplicate_groups(&concept_strings, max_edit_distance);
+    let mut fuzzy_group_of = vec![0usize; concepts.len()];
+    for (group_id, indices) in fuzzy_groups.iter().enumerate() {
+        for &index in indices {
+            fuzzy_group_of[index] = group_id;
+        }
+    }
+
     let mut merged_groups = Vec::new();
     let mut processed = HashSet::new();
-    
+
     for i in 0..concepts.len() {
         if processed.contains(&i) {
             continue;
         }
-        
-        // Find similar concepts
+
+        // Find similar concepts: either string-level fuzzy duplicates or
+        // cosine-similar embeddings.
         let mut similar_indices = vec![i];
         for j in (i + 1)..concepts.len() {
             if !processed.contains(&j) {
-                let similarity = cosine_similarity(
-                    embeddings[i].view(),
-                    embeddings[j].view()
+                let fuzzy_match = fuzzy_group_of[j] == fuzzy_group_of[i];
+                let similarity = calibrate(
+                    cosine_similarity(embeddings[i].view(), embeddings[j].view()),
+                    calibration,
                 );
-                
-                if similarity > similarity_threshold {
+
+                if fuzzy_match || similarity > similarity_threshold {
                     similar_indices.push(j);
                 }
             }
@@ -134,6 +173,117 @@ pub fn cluster_embeddings(
     Ok(predictions.targets.iter().map(|&x| x as usize).collect())
 }
 
+/// Clusters product-quantized embeddings by reconstructing each through
+/// `quantizer` and delegating to `cluster_embeddings`. `linfa`'s k-means has
+/// no notion of quantized codes, so the dense reconstruction here is
+/// transient; the scaling win is that only `quantized`'s `m` bytes per
+/// vector, not the full dense corpus, need to be kept in memory between
+/// calls.
+pub fn cluster_quantized_embeddings(
+    quantizer: &ProductQuantizer,
+    quantized: &[QuantizedEmbedding],
+    n_clusters: usize,
+) -> Result<Vec<usize>, ApiError> {
+    let reconstructed: Vec<Embedding> = quantized.iter().map(|q| quantizer.reconstruct(q)).collect();
+    cluster_embeddings(&reconstructed, n_clusters)
+}
+
+/// Mean silhouette score of `assignments` over `embeddings`, using
+/// `1 - cosine_similarity` as the pairwise distance. For each point,
+/// `a` is its mean distance to other points in its own cluster and `b`
+/// is the lowest mean distance to any other cluster's points; the
+/// silhouette is `(b - a) / max(a, b)`. Points in a singleton cluster, or
+/// assignments with only one cluster overall, have no `a` or `b` to compare
+/// and contribute `0`.
+pub fn silhouette_score(embeddings: &[Embedding], assignments: &[usize]) -> f32 {
+    let n = embeddings.len();
+    if n == 0 {
+        return 0.0;
+    }
+
+    let distance = |a: &Embedding, b: &Embedding| 1.0 - cosine_similarity(a.view(), b.view());
+
+    let total: f32 = (0..n)
+        .map(|i| {
+            let own_cluster = assignments[i];
+            let same_cluster: Vec<usize> = (0..n)
+                .filter(|&j| j != i && assignments[j] == own_cluster)
+                .collect();
+            let other_clusters: HashSet<usize> = assignments
+                .iter()
+                .copied()
+                .filter(|&cluster| cluster != own_cluster)
+                .collect();
+
+            if same_cluster.is_empty() || other_clusters.is_empty() {
+                return 0.0;
+            }
+
+            let a = same_cluster
+                .iter()
+                .map(|&j| distance(&embeddings[i], &embeddings[j]))
+                .sum::<f32>()
+                / same_cluster.len() as f32;
+
+            let b = other_clusters
+                .iter()
+                .map(|&cluster| {
+                    let members: Vec<usize> = (0..n).filter(|&j| assignments[j] == cluster).collect();
+                    members
+                        .iter()
+                        .map(|&j| distance(&embeddings[i], &embeddings[j]))
+                        .sum::<f32>()
+                        / members.len() as f32
+                })
+                .fold(f32::MAX, f32::min);
+
+            (b - a) / a.max(b)
+        })
+        .sum();
+
+    total / n as f32
+}
+
+/// Sweeps `k_range`, fits `cluster_embeddings` for each `k`, and returns the
+/// `k` (and its assignments) that maximizes the mean silhouette score. Pass
+/// a single-value range (e.g. `3..=3`) to pin `k` to a fixed value, matching
+/// the old hardcoded-`n_clusters` behavior.
+pub fn select_cluster_count(
+    embeddings: &[Embedding],
+    k_range: RangeInclusive<usize>,
+) -> Result<(usize, Vec<usize>), ApiError> {
+    if embeddings.is_empty() {
+        return Err(ApiError::InternalError("Empty embeddings".to_string()));
+    }
+
+    let max_k = embeddings.len();
+    let mut best: Option<(usize, Vec<usize>, f32)> = None;
+
+    for k in *k_range.start()..=(*k_range.end()).min(max_k) {
+        if k == 0 {
+            continue;
+        }
+
+        let assignments = cluster_embeddings(embeddings, k)?;
+        let score = silhouette_score(embeddings, &assignments);
+
+        let is_better = match &best {
+            Some((_, _, best_score)) => score > *best_score,
+            None => true,
+        };
+        if is_better {
+            best = Some((k, assignments, score));
+        }
+    }
+
+    best.map(|(k, assignments, _)| (k, assignments)).ok_or_else(|| {
+        ApiError::InternalError(format!(
+            "No valid cluster count in range for {} embeddings",
+            embeddings.len()
+        ))
+    })
+}
+
 // Reduce dimensionality to 3D
 pub fn reduce_to_3d(embeddings: &[Embedding]) -> Result<Vec<[f32; 3]>, ApiError> {
     if embeddings.is_empty() {
@@ -176,30 +326,50 @@ pub fn reduce_to_3d(embeddings: &[Embedding]) -> Result<Vec<[f32; 3]>, ApiError>
     Ok(reduced)
 }
 
+/// Reduces product-quantized embeddings to 3D by reconstructing each
+/// through `quantizer` and delegating to `reduce_to_3d`, for the same
+/// reason `cluster_quantized_embeddings` reconstructs: PCA has no notion of
+/// quantized codes, so only the compressed corpus needs to be retained
+/// between calls.
+pub fn reduce_quantized_to_3d(
+    quantizer: &ProductQuantizer,
+    quantized: &[QuantizedEmbedding],
+) -> Result<Vec<[f32; 3]>, ApiError> {
+    let reconstructed: Vec<Embedding> = quantized.iter().map(|q| quantizer.reconstruct(q)).collect();
+    reduce_to_3d(&reconstructed)
+}
+
 pub fn cluster_concepts(
     concepts: &[Concept],
     embeddings: &[Embedding],
+    calibration: Option<&SimilarityCalibration>,
 ) -> Result<Vec<ConceptGroup>, ApiError> {
-    // Merge similar concepts
-    let merged_groups = merge_similar_concepts(concepts, embeddings, 0.8)?;
+    // Merge similar concepts. A max edit distance of 1 catches minor surface
+    // variation (typos, pluralization) without over-merging unrelated short
+    // concepts.
+    let merged_groups = merge_similar_concepts(concepts, embeddings, 0.8, calibration, 1)?;
     
     // Extract embeddings for clustering
     let merged_embeddings: Vec<Embedding> = merged_groups.iter()
         .map(|(_, embedding)| embedding.clone())
         .collect();
     
-    // Apply clustering
-    let n_clusters = 3.min(merged_embeddings.len());
-    let clusters = cluster_embeddings(&merged_embeddings, n_clusters)?;
+    // Apply clustering, choosing the cluster count that maximizes the mean
+    // silhouette score rather than a fixed k. Callers that want the old
+    // fixed k=3 behavior can call `select_cluster_count` directly with a
+    // `3..=3` range.
+    let max_k = 6.min(merged_embeddings.len()).max(1);
+    let (_n_clusters, clusters) = select_cluster_count(&merged_embeddings, 1..=max_k)?;
     
     // Reduce dimensions for visualization
     let reduced_embeddings = reduce_to_3d(&merged_embeddings)?;
     
     // Create final groups
     let mut final_groups = Vec::new();
-    for (i, ((concepts, _), reduced)) in merged_groups.iter().zip(reduced_embeddings).enumerate() {
+    for (i, ((concepts, embedding), reduced)) in merged_groups.iter().zip(reduced_embeddings).enumerate() {
         final_groups.push(ConceptGroup {
             concepts: concepts.clone(),
+            embedding: embedding.to_vec(),
             reduced_embedding: reduced.to_vec(),
             cluster: clusters[i],
         });
@@ -218,4 +388,309 @@ pub fn cluster_concepts(
     }
     
     Ok(final_groups)
+}
+
+/// A label + unit-normalized embedding pair, indexed for `similarity`/
+/// `analogy` queries. Entries are normalized once when the index is built;
+/// each query then scores every entry with `cosine_similarity`, which still
+/// computes both vectors' norms on every call.
+pub struct EmbeddingIndex {
+    labels: Vec<String>,
+    normalized: Vec<Embedding>,
+}
+
+impl EmbeddingIndex {
+    /// Builds an index over raw `(label, embedding)` pairs, normalizing
+    /// each embedding to unit length up front.
+    pub fn new(entries: Vec<(String, Embedding)>) -> Self {
+        let (labels, normalized) = entries
+            .into_iter()
+            .map(|(label, embedding)| (label, search::normalize(&embedding)))
+            .unzip();
+
+        Self { labels, normalized }
+    }
+
+    /// Builds an index over a set of clustered `ConceptGroup`s, so a query
+    /// can surface merged/averaged groups rather than only raw concepts.
+    pub fn from_concept_groups(groups: &[ConceptGroup]) -> Self {
+        Self::new(
+            groups
+                .iter()
+                .map(|group| (group.concepts.join(", "), Array1::from(group.embedding.clone())))
+                .collect(),
+        )
+    }
+
+    /// Returns the `top_k` indexed entries most similar to `query` by
+    /// cosine similarity, descending.
+    pub fn similarity(&self, query: &Embedding, top_k: usize) -> Vec<(String, f32)> {
+        let normalized_query = search::normalize(query);
+
+        let mut scored: Vec<(String, f32)> = self
+            .labels
+            .iter()
+            .zip(&self.normalized)
+            .map(|(label, embedding)| {
+                (
+                    label.clone(),
+                    cosine_similarity(embedding.view(), normalized_query.view()),
+                )
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(top_k);
+        scored
+    }
+
+    /// Solves `a : b :: c : ?` by computing `b - a + c`, normalizing, and
+    /// returning the `top_k` nearest entries with `a`, `b`, and `c`
+    /// themselves excluded from the results.
+    pub fn analogy(
+        &self,
+        a: &str,
+        b: &str,
+        c: &str,
+        top_k: usize,
+    ) -> Result<Vec<(String, f32)>, ApiError> {
+        let vector_a = self.vector_for(a)?;
+        let vector_b = self.vector_for(b)?;
+        let vector_c = self.vector_for(c)?;
+
+        let target = search::normalize(&(vector_b - vector_a + vector_c));
+
+        let mut scored: Vec<(String, f32)> = self
+            .labels
+            .iter()
+            .zip(&self.normalized)
+            .filter(|(label, _)| label.as_str() != a && label.as_str() != b && label.as_str() != c)
+            .map(|(label, embedding)| (label.clone(), cosine_similarity(embedding.view(), target.view())))
+            .collect();
+
+        scored.sort_by(|x, y| y.1.partial_cmp(&x.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(top_k);
+        Ok(scored)
+    }
+
+    fn vector_for(&self, label: &str) -> Result<Embedding, ApiError> {
+        self.labels
+            .iter()
+            .position(|candidate| candidate == label)
+            .map(|index| self.normalized[index].clone())
+            .ok_or_else(|| {
+                ApiError::InternalError(format!("Unknown concept for analogy query: {}", label))
+            })
+    }
+}
+
+/// Like `EmbeddingIndex`, but stores each entry as a `QuantizedEmbedding`
+/// behind a shared `ProductQuantizer` instead of a full-precision vector,
+/// so a corpus too large to hold densely in memory can still be queried.
+/// `similarity` runs entirely through `ProductQuantizer`'s precomputed
+/// distance-table path — no reconstruction.
+pub struct QuantizedEmbeddingIndex {
+    quantizer: ProductQuantizer,
+    labels: Vec<String>,
+    quantized: Vec<QuantizedEmbedding>,
+}
+
+impl QuantizedEmbeddingIndex {
+    /// Encodes each `(label, embedding)` pair through `quantizer` up front.
+    pub fn new(quantizer: ProductQuantizer, entries: Vec<(String, Embedding)>) -> Self {
+        let (labels, quantized) = entries
+            .into_iter()
+            .map(|(label, embedding)| (label, quantizer.encode(&embedding)))
+            .unzip();
+
+        Self {
+            quantizer,
+            labels,
+            quantized,
+        }
+    }
+
+    /// Returns the `top_k` indexed entries most similar to `query` by
+    /// approximate cosine similarity, descending. Builds one distance
+    /// table for `query` and reuses it across every stored entry.
+    pub fn similarity(&self, query: &Embedding, top_k: usize) -> Vec<(String, f32)> {
+        let table = self.quantizer.distance_table(query);
+
+        let mut scored: Vec<(String, f32)> = self
+            .labels
+            .iter()
+            .zip(&self.quantized)
+            .map(|(label, quantized)| {
+                (
+                    label.clone(),
+                    cosine_similarity_quantized(&self.quantizer, &table, quantized),
+                )
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(top_k);
+        scored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> EmbeddingIndex {
+        EmbeddingIndex::new(vec![
+            ("king".to_string(), Array1::from(vec![1.0, 1.0, 0.0])),
+            ("queen".to_string(), Array1::from(vec![0.0, 1.0, 1.0])),
+            ("man".to_string(), Array1::from(vec![1.0, 0.0, 0.0])),
+            ("woman".to_string(), Array1::from(vec![0.0, 0.0, 1.0])),
+            ("unrelated".to_string(), Array1::from(vec![-1.0, -1.0, -1.0])),
+        ])
+    }
+
+    #[test]
+    fn test_similarity_orders_by_cosine() {
+        let index = index();
+        let query = Array1::from(vec![1.0, 1.0, 0.0]);
+        let results = index.similarity(&query, 2);
+        assert_eq!(results[0].0, "king");
+        assert!(results[0].1 >= results[1].1);
+    }
+
+    #[test]
+    fn test_analogy_excludes_input_terms() {
+        let index = index();
+        let results = index.analogy("man", "king", "woman", 3).unwrap();
+        assert!(!results.iter().any(|(label, _)| label == "man"));
+        assert!(!results.iter().any(|(label, _)| label == "king"));
+        assert!(!results.iter().any(|(label, _)| label == "woman"));
+    }
+
+    #[test]
+    fn test_analogy_unknown_term_errors() {
+        let index = index();
+        assert!(index.analogy("man", "king", "missing", 3).is_err());
+    }
+
+    fn concept(text: &str) -> Concept {
+        Concept {
+            concept: text.to_string(),
+            source_range: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_similar_concepts_fuzzy_merges_typos_below_cosine_threshold() {
+        let concepts = vec![concept("color"), concept("colour"), concept("shape")];
+        // Deliberately dissimilar embeddings so only the fuzzy string match,
+        // not cosine similarity, could explain "color"/"colour" merging.
+        let embeddings = vec![
+            Array1::from(vec![1.0, 0.0]),
+            Array1::from(vec![0.0, 1.0]),
+            Array1::from(vec![-1.0, -1.0]),
+        ];
+
+        let merged = merge_similar_concepts(&concepts, &embeddings, 0.99, None, 1).unwrap();
+        let colorgroup = merged
+            .iter()
+            .find(|(names, _)| names.contains(&"color".to_string()))
+            .unwrap();
+        assert!(colorgroup.0.contains(&"colour".to_string()));
+        assert!(!colorgroup.0.contains(&"shape".to_string()));
+    }
+
+    #[test]
+    fn test_merge_similar_concepts_zero_edit_distance_keeps_typos_separate() {
+        let concepts = vec![concept("color"), concept("colour")];
+        let embeddings = vec![Array1::from(vec![1.0, 0.0]), Array1::from(vec![0.0, 1.0])];
+
+        let merged = merge_similar_concepts(&concepts, &embeddings, 0.99, None, 0).unwrap();
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn test_silhouette_score_is_zero_for_single_cluster() {
+        let embeddings = vec![
+            Array1::from(vec![1.0, 0.0]),
+            Array1::from(vec![0.9, 0.1]),
+            Array1::from(vec![-1.0, 0.0]),
+        ];
+        let assignments = vec![0, 0, 0];
+        assert_eq!(silhouette_score(&embeddings, &assignments), 0.0);
+    }
+
+    #[test]
+    fn test_silhouette_score_rewards_well_separated_clusters() {
+        let embeddings = vec![
+            Array1::from(vec![1.0, 0.0]),
+            Array1::from(vec![0.95, 0.05]),
+            Array1::from(vec![-1.0, 0.0]),
+            Array1::from(vec![-0.95, -0.05]),
+        ];
+        let good = vec![0, 0, 1, 1];
+        let bad = vec![0, 1, 0, 1];
+        assert!(silhouette_score(&embeddings, &good) > silhouette_score(&embeddings, &bad));
+    }
+
+    #[test]
+    fn test_select_cluster_count_prefers_true_structure_over_fixed_three() {
+        // Two well-separated clusters of two points each: k=2 should score
+        // higher than forcing k=3 or higher.
+        let embeddings = vec![
+            Array1::from(vec![1.0, 0.0]),
+            Array1::from(vec![0.95, 0.05]),
+            Array1::from(vec![-1.0, 0.0]),
+            Array1::from(vec![-0.95, -0.05]),
+        ];
+        let (k, assignments) = select_cluster_count(&embeddings, 1..=4).unwrap();
+        assert_eq!(k, 2);
+        assert_eq!(assignments.len(), embeddings.len());
+    }
+
+    #[test]
+    fn test_quantized_embedding_index_similarity_matches_dense_ordering() {
+        let entries = vec![
+            ("king".to_string(), Array1::from(vec![1.0, 1.0, 0.0, 0.0])),
+            ("queen".to_string(), Array1::from(vec![0.9, 1.1, 0.0, 0.0])),
+            ("unrelated".to_string(), Array1::from(vec![0.0, 0.0, -1.0, -1.0])),
+        ];
+
+        let training: Vec<Embedding> = entries.iter().map(|(_, e)| e.clone()).collect();
+        let quantizer = ProductQuantizer::train(&training, 2).unwrap();
+
+        let index = QuantizedEmbeddingIndex::new(quantizer, entries);
+        let query = Array1::from(vec![1.0, 1.0, 0.0, 0.0]);
+        let results = index.similarity(&query, 2);
+
+        assert_eq!(results[0].0, "king");
+        assert!(results[0].1 >= results[1].1);
+    }
+
+    #[test]
+    fn test_cluster_quantized_embeddings_matches_dense_cluster_count() {
+        let embeddings = vec![
+            Array1::from(vec![1.0, 0.0, 1.0, 0.0]),
+            Array1::from(vec![0.95, 0.05, 0.95, 0.05]),
+            Array1::from(vec![-1.0, 0.0, -1.0, 0.0]),
+            Array1::from(vec![-0.95, -0.05, -0.95, -0.05]),
+        ];
+        let quantizer = ProductQuantizer::train(&embeddings, 2).unwrap();
+        let quantized: Vec<QuantizedEmbedding> =
+            embeddings.iter().map(|e| quantizer.encode(e)).collect();
+
+        let assignments = cluster_quantized_embeddings(&quantizer, &quantized, 2).unwrap();
+        assert_eq!(assignments.len(), embeddings.len());
+    }
+
+    #[test]
+    fn test_select_cluster_count_single_value_range_pins_k() {
+        let embeddings = vec![
+            Array1::from(vec![1.0, 0.0]),
+            Array1::from(vec![-1.0, 0.0]),
+            Array1::from(vec![0.0, 1.0]),
+        ];
+        let (k, assignments) = select_cluster_count(&embeddings, 3..=3).unwrap();
+        assert_eq!(k, 3);
+        assert_eq!(assignments.len(), 3);
+    }
 }
\ No newline at end of file
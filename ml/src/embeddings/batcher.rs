@@ -0,0 +1,203 @@
+use crate::embeddings::calibration::SimilarityCalibration;
+use crate::embeddings::model::Embedding;
+use crate::embeddings::provider::EmbeddingProvider;
+use crate::error::ApiError;
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{timeout, Instant};
+
+/// Maximum number of individual embedding requests coalesced into one
+/// downstream `get_batch_embeddings` call.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 32;
+
+/// Maximum time a batch waits for more requests before dispatching with
+/// whatever it has collected so far.
+pub const DEFAULT_MAX_BATCH_WAIT: Duration = Duration::from_millis(50);
+
+struct BatchItem {
+    text: String,
+    respond_to: oneshot::Sender<Result<Embedding, ApiError>>,
+}
+
+/// Wraps an `EmbeddingProvider` with cross-request micro-batching: concurrent
+/// callers each `await` a single embedding via `get_contextual_embeddings`,
+/// but under the hood requests are collected into windows of at most
+/// `max_batch_size` items (or `max_batch_wait`, whichever comes first) and
+/// dispatched as one combined call to the wrapped provider. This cuts
+/// round-trips under load without handlers having to batch anything
+/// themselves.
+pub struct EmbeddingBatcher {
+    provider: Arc<dyn EmbeddingProvider>,
+    sender: mpsc::UnboundedSender<BatchItem>,
+}
+
+impl EmbeddingBatcher {
+    pub fn new(provider: Arc<dyn EmbeddingProvider>) -> Self {
+        Self::with_batch_params(provider, DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_BATCH_WAIT)
+    }
+
+    /// Overrides the batch size/wait window, e.g. for tests or for tuning
+    /// to a specific provider's throughput.
+    pub fn with_batch_params(
+        provider: Arc<dyn EmbeddingProvider>,
+        max_batch_size: usize,
+        max_batch_wait: Duration,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let worker_provider = Arc::clone(&provider);
+        tokio::spawn(run_batcher(
+            worker_provider,
+            receiver,
+            max_batch_size.max(1),
+            max_batch_wait,
+        ));
+
+        Self { provider, sender }
+    }
+}
+
+/// Background task that drains `receiver`, groups waiting requests into
+/// windows bounded by `max_batch_size`/`max_batch_wait`, and dispatches each
+/// window as a single `get_batch_embeddings` call. Runs until every
+/// `EmbeddingBatcher` handle (and its sender) is dropped.
+async fn run_batcher(
+    provider: Arc<dyn EmbeddingProvider>,
+    mut receiver: mpsc::UnboundedReceiver<BatchItem>,
+    max_batch_size: usize,
+    max_batch_wait: Duration,
+) {
+    while let Some(first) = receiver.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + max_batch_wait;
+
+        while batch.len() < max_batch_size {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            match timeout(remaining, receiver.recv()).await {
+                Ok(Some(item)) => batch.push(item),
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        dispatch_batch(&provider, batch).await;
+    }
+}
+
+/// Issues one `get_batch_embeddings` call for `batch` and fans the results
+/// (or a shared error) back to each caller's oneshot channel.
+async fn dispatch_batch(provider: &Arc<dyn EmbeddingProvider>, batch: Vec<BatchItem>) {
+    let texts: Vec<String> = batch.iter().map(|item| item.text.clone()).collect();
+
+    match provider.get_batch_embeddings(&texts).await {
+        Ok(embeddings) if embeddings.len() == batch.len() => {
+            for (item, embedding) in batch.into_iter().zip(embeddings) {
+                let _ = item.respond_to.send(Ok(embedding));
+            }
+        }
+        Ok(embeddings) => {
+            let message = format!(
+                "Batched embedding call returned {} results for {} inputs",
+                embeddings.len(),
+                batch.len()
+            );
+            for item in batch {
+                let _ = item.respond_to.send(Err(ApiError::InternalError(message.clone())));
+            }
+        }
+        Err(err) => {
+            let message = err.to_string();
+            for item in batch {
+                let _ = item.respond_to.send(Err(ApiError::InternalError(message.clone())));
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for EmbeddingBatcher {
+    /// Enqueues `text` for the next batch window and awaits its result,
+    /// transparently coalescing with whatever other callers are waiting at
+    /// the same time.
+    async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError> {
+        if text.is_empty() {
+            return Err(ApiError::InternalError("Empty text provided".to_string()));
+        }
+
+        let (respond_to, receiver) = oneshot::channel();
+        self.sender
+            .send(BatchItem {
+                text: text.to_string(),
+                respond_to,
+            })
+            .map_err(|_| {
+                ApiError::InternalError("Embedding batcher task has shut down".to_string())
+            })?;
+
+        receiver.await.map_err(|_| {
+            ApiError::InternalError("Embedding batcher dropped the request".to_string())
+        })?
+    }
+
+    /// Routes every text through the same per-item batching path so a
+    /// caller's own batch call still coalesces with concurrent requests
+    /// from other handlers.
+    ///
+    /// One future per input, in input order: the returned `Vec` is always
+    /// exactly `texts.len()` long and index-aligned with it, which callers
+    /// like `main.rs::embed_new_concepts` rely on to zip results back onto
+    /// their own index list. A text that's empty after trimming gets a
+    /// zero-vector placeholder instead of being dropped or failing the
+    /// whole batch.
+    async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        let requests = texts.iter().map(|text| {
+            let text = text.trim().to_string();
+            async move {
+                if text.is_empty() {
+                    return Ok(Embedding::zeros(self.dimensions()));
+                }
+                self.get_contextual_embeddings(&text).await
+            }
+        });
+
+        try_join_all(requests).await
+    }
+
+    fn dimensions(&self) -> usize {
+        self.provider.dimensions()
+    }
+
+    fn id(&self) -> &str {
+        self.provider.id()
+    }
+
+    fn similarity_calibration(&self) -> Option<&SimilarityCalibration> {
+        self.provider.similarity_calibration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeddings::local::LocalEmbeddingProvider;
+
+    #[tokio::test]
+    async fn test_get_batch_embeddings_stays_index_aligned_with_empty_entries() {
+        let batcher = EmbeddingBatcher::new(Arc::new(LocalEmbeddingProvider::new()));
+        let texts = vec![
+            "hello world".to_string(),
+            "   ".to_string(),
+            "goodbye".to_string(),
+        ];
+
+        let embeddings = batcher.get_batch_embeddings(&texts).await.unwrap();
+
+        assert_eq!(embeddings.len(), texts.len());
+        assert!(embeddings[1].iter().all(|&v| v == 0.0));
+    }
+}
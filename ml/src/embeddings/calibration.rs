@@ -0,0 +1,59 @@
+/// Per-model calibration for raw cosine similarity scores.
+///
+/// Raw cosine similarities from a given embedding model tend to cluster in a
+/// narrow band (e.g. 0.6-0.9 for real pairs), which makes thresholds hard to
+/// reason about. This remaps a raw score `s` via a sigmoid recentering
+/// around the model's observed mean (`mean`) and spread (`std`), spreading
+/// scores back across the full 0-1 range.
+#[derive(Debug, Clone, Copy)]
+pub struct SimilarityCalibration {
+    pub mean: f32,
+    pub std: f32,
+}
+
+impl SimilarityCalibration {
+    pub fn new(mean: f32, std: f32) -> Self {
+        Self { mean, std }
+    }
+
+    /// Remaps `raw` via `1 / (1 + exp(-(raw - mean) / std))`.
+    pub fn apply(&self, raw: f32) -> f32 {
+        if self.std == 0.0 {
+            return raw;
+        }
+        1.0 / (1.0 + (-(raw - self.mean) / self.std).exp())
+    }
+}
+
+/// Applies `calibration` to `raw` if present, otherwise returns `raw`
+/// unchanged so callers that haven't configured calibration keep today's
+/// behavior.
+pub fn calibrate(raw: f32, calibration: Option<&SimilarityCalibration>) -> f32 {
+    calibration.map_or(raw, |c| c.apply(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_when_unset() {
+        assert_eq!(calibrate(0.73, None), 0.73);
+    }
+
+    #[test]
+    fn test_mean_maps_to_half() {
+        let calibration = SimilarityCalibration::new(0.75, 0.1);
+        let score = calibrate(0.75, Some(&calibration));
+        assert!((score - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_spreads_scores_around_mean() {
+        let calibration = SimilarityCalibration::new(0.75, 0.1);
+        let low = calibrate(0.6, Some(&calibration));
+        let high = calibrate(0.9, Some(&calibration));
+        assert!(low < 0.5);
+        assert!(high > 0.5);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::embeddings::model::Embedding;
+use crate::error::ApiError;
+use async_trait::async_trait;
+
+/// Common interface for anything that can turn text into a vector embedding.
+///
+/// `EmbeddingModel` implements this against Ollama's fixed `/api/embeddings`
+/// shape; `TemplateRestEmbedder` implements it against an arbitrary REST API
+/// described by JSON templates, so callers can swap providers without
+/// touching the rest of the pipeline.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    async fn embed(&self, text: &str) -> Result<Embedding, ApiError>;
+
+    /// Default sequential implementation; providers with a native batch
+    /// endpoint or a concurrency budget can override this.
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            let text = text.trim();
+            if !text.is_empty() {
+                embeddings.push(self.embed(text).await?);
+            }
+        }
+        Ok(embeddings)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::embeddings::model::Embedding;
+use crate::embeddings::provider::EmbeddingProvider;
+use crate::error::ApiError;
+use async_trait::async_trait;
+use ndarray::Array1;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Dimensionality of the local hashing embedder's output vectors.
+pub const DEFAULT_LOCAL_DIMENSIONS: usize = 256;
+
+/// An in-process `EmbeddingProvider` with no network dependency: words are
+/// hashed into fixed-size buckets (the "hashing trick") and the resulting
+/// vector is L2-normalized. It trades semantic quality for zero external
+/// dependencies, useful for offline development or as a fallback when no
+/// embedding service is configured.
+pub struct LocalEmbeddingProvider {
+    dimensions: usize,
+    id: String,
+}
+
+impl LocalEmbeddingProvider {
+    pub fn new() -> Self {
+        Self::with_dimensions(DEFAULT_LOCAL_DIMENSIONS)
+    }
+
+    pub fn with_dimensions(dimensions: usize) -> Self {
+        Self {
+            dimensions,
+            id: format!("local:hashing-{}", dimensions),
+        }
+    }
+
+    fn embed_text(&self, text: &str) -> Embedding {
+        let mut buckets = vec![0f32; self.dimensions];
+
+        for word in text.split_whitespace() {
+            let mut hasher = DefaultHasher::new();
+            word.to_lowercase().hash(&mut hasher);
+            let bucket = (hasher.finish() as usize) % self.dimensions;
+            buckets[bucket] += 1.0;
+        }
+
+        let norm = buckets.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for value in &mut buckets {
+                *value /= norm;
+            }
+        }
+
+        Array1::from(buckets)
+    }
+}
+
+impl Default for LocalEmbeddingProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for LocalEmbeddingProvider {
+    async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError> {
+        if text.is_empty() {
+            return Err(ApiError::InternalError("Empty text provided".to_string()));
+        }
+        Ok(self.embed_text(text))
+    }
+
+    async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        Ok(texts
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .map(|t| self.embed_text(t))
+            .collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_embeds_to_configured_dimensions() {
+        let provider = LocalEmbeddingProvider::with_dimensions(64);
+        let embedding = provider
+            .get_contextual_embeddings("hello world")
+            .await
+            .unwrap();
+        assert_eq!(embedding.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_same_text_is_deterministic() {
+        let provider = LocalEmbeddingProvider::new();
+        let a = provider
+            .get_contextual_embeddings("the quick brown fox")
+            .await
+            .unwrap();
+        let b = provider
+            .get_contextual_embeddings("the quick brown fox")
+            .await
+            .unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[tokio::test]
+    async fn test_empty_text_is_rejected() {
+        let provider = LocalEmbeddingProvider::new();
+        assert!(provider.get_contextual_embeddings("").await.is_err());
+    }
+}
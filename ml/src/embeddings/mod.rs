@@ -0,0 +1,20 @@
+pub mod batcher;
+pub mod calibration;
+pub mod embedder;
+pub mod local;
+pub mod model;
+pub mod openai;
+pub mod pretrained;
+pub mod provider;
+pub mod quantized;
+pub mod rest;
+pub mod template;
+
+pub use batcher::EmbeddingBatcher;
+pub use calibration::SimilarityCalibration;
+pub use embedder::Embedder;
+pub use model::*;
+pub use pretrained::{load_pretrained, EmbeddingMatrix, PretrainedEmbeddingProvider, PretrainedFormat, Vocab};
+pub use provider::{provider_from_env, EmbeddingProvider};
+pub use quantized::{DistanceTable, ProductQuantizer, QuantizedEmbedding};
+pub use rest::TemplateRestEmbedder;
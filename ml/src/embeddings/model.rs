@@ -1,5 +1,11 @@
+use crate::embeddings::calibration::{calibrate, SimilarityCalibration};
+use crate::embeddings::embedder::Embedder;
+use crate::embeddings::provider::EmbeddingProvider;
 use crate::error::ApiError;
-use log::{debug, info};
+use crate::retry::{backoff_delay, classify_error, RetryDecision, DEFAULT_MAX_ATTEMPTS};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use log::{debug, info, warn};
 use ndarray::{Array1, ArrayBase, Dim, OwnedRepr};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -7,6 +13,13 @@ use std::time::Duration;
 
 pub type Embedding = Array1<f32>;
 
+/// Default number of in-flight embedding requests `get_batch_embeddings`
+/// will issue at once when the caller doesn't configure its own.
+pub const DEFAULT_REQUEST_PARALLELISM: usize = 8;
+
+/// Dimensionality of `snowflake-arctic-embed2`, the default Ollama model.
+pub const DEFAULT_OLLAMA_DIMENSIONS: usize = 1024;
+
 #[derive(Debug, Serialize)]
 struct EmbeddingRequest {
     model: String,
@@ -22,6 +35,10 @@ pub struct EmbeddingModel {
     base_url: String,
     client: Client,
     model_name: String,
+    id: String,
+    dimensions: usize,
+    request_parallelism: usize,
+    similarity_calibration: Option<SimilarityCalibration>,
 }
 
 impl EmbeddingModel {
@@ -31,30 +48,73 @@ impl EmbeddingModel {
             .build()
             .expect("Failed to create HTTP client");
 
+        let model_name = "snowflake-arctic-embed2".to_string();
+        let id = format!("ollama:{}", model_name);
+
         Self {
             base_url: base_url.to_string(),
             client,
-            model_name: "snowflake-arctic-embed2".to_string(),
+            model_name,
+            id,
+            dimensions: DEFAULT_OLLAMA_DIMENSIONS,
+            request_parallelism: DEFAULT_REQUEST_PARALLELISM,
+            similarity_calibration: None,
         }
     }
 
+    /// Overrides how many embedding requests `get_batch_embeddings` keeps
+    /// in flight at once, so callers can tune it to their backend's
+    /// capacity (a beefier remote API vs. a single local Ollama instance).
+    pub fn with_request_parallelism(mut self, request_parallelism: usize) -> Self {
+        self.request_parallelism = request_parallelism.max(1);
+        self
+    }
+
+    /// Configures the mean/std of this model's raw cosine similarity
+    /// distribution so `calibrate_similarity` can recenter scores across
+    /// the full 0-1 range. Left unset, `calibrate_similarity` is the
+    /// identity function.
+    pub fn with_similarity_calibration(mut self, mean: f32, std: f32) -> Self {
+        self.similarity_calibration = Some(SimilarityCalibration::new(mean, std));
+        self
+    }
+
+    /// Remaps a raw cosine similarity using this model's calibration, or
+    /// returns it unchanged if none has been configured.
+    pub fn calibrate_similarity(&self, raw: f32) -> f32 {
+        calibrate(raw, self.similarity_calibration.as_ref())
+    }
+
+    /// The mean/std calibration configured for this model, if any.
+    pub fn similarity_calibration(&self) -> Option<&SimilarityCalibration> {
+        self.similarity_calibration.as_ref()
+    }
+
     pub async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
-        let mut embeddings = Vec::new();
-
-        for text in texts {
-            let text = text.trim();
-            if !text.is_empty() {
-                debug!(
-                    "Processing: '{}'",
-                    &text.chars().take(50).collect::<String>()
-                );
-
-                let embedding: ArrayBase<OwnedRepr<f32>, Dim<[usize; 1]>> = self.get_contextual_embeddings(text).await?;
-                embeddings.push(embedding);
-            }
+        let non_empty: Vec<&str> = texts
+            .iter()
+            .map(|text| text.trim())
+            .filter(|text| !text.is_empty())
+            .collect();
+
+        for text in &non_empty {
+            debug!(
+                "Processing: '{}'",
+                text.chars().take(50).collect::<String>()
+            );
         }
 
-        Ok(embeddings)
+        // `buffer_unordered` completes requests in whatever order they
+        // finish, so each result is tagged with its original index and
+        // sorted back into input order before returning.
+        let mut indexed: Vec<(usize, Result<Embedding, ApiError>)> = stream::iter(non_empty.into_iter().enumerate())
+            .map(|(index, text)| async move { (index, self.get_contextual_embeddings(text).await) })
+            .buffer_unordered(self.request_parallelism)
+            .collect()
+            .await;
+
+        indexed.sort_by_key(|(index, _)| *index);
+        indexed.into_iter().map(|(_, result)| result).collect()
     }
 
     pub async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError> {
@@ -62,9 +122,50 @@ impl EmbeddingModel {
             return Err(ApiError::InternalError("Empty text provided".to_string()));
         }
 
+        self.request_embedding_with_retry(text).await
+    }
+
+    /// Sends the embedding request, retrying transient failures with
+    /// exponential backoff. On a "prompt too long" rejection the text is
+    /// progressively truncated and resubmitted instead of waiting.
+    async fn request_embedding_with_retry(&self, text: &str) -> Result<Embedding, ApiError> {
+        let mut prompt = text.to_string();
+        let mut last_err: Option<ApiError> = None;
+
+        for attempt in 1..=DEFAULT_MAX_ATTEMPTS {
+            match self.post_embedding(&prompt).await {
+                Ok(embedding) => return Ok(embedding),
+                Err((decision, err)) => {
+                    if attempt == DEFAULT_MAX_ATTEMPTS || decision == RetryDecision::GiveUp {
+                        return Err(err);
+                    }
+
+                    warn!(
+                        "Embedding request failed ({:?}) on attempt {}/{}: {}",
+                        decision, attempt, DEFAULT_MAX_ATTEMPTS, err
+                    );
+
+                    if decision == RetryDecision::RetryTokenized {
+                        prompt = truncate_prompt(&prompt);
+                    }
+
+                    last_err = Some(err);
+                    tokio::time::sleep(backoff_delay(attempt, decision)).await;
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or(ApiError::InternalError(
+            "Embedding request exhausted retries".to_string(),
+        )))
+    }
+
+    /// Single POST attempt. Returns the classified retry decision alongside
+    /// the error so the retry loop can decide what to do next.
+    async fn post_embedding(&self, prompt: &str) -> Result<Embedding, (RetryDecision, ApiError)> {
         let request: EmbeddingRequest = EmbeddingRequest {
             model: self.model_name.clone(),
-            prompt: text.to_string(),
+            prompt: prompt.to_string(),
         };
 
         let url: String = format!("{}/api/embeddings", self.base_url);
@@ -78,21 +179,22 @@ impl EmbeddingModel {
             .await
             .map_err(|e| {
                 info!("Error requesting embeddings: {}", e);
-                ApiError::RequestError(e)
+                (RetryDecision::Retry, ApiError::RequestError(e))
             })?;
 
         if !response.status().is_success() {
             let status: reqwest::StatusCode = response.status();
             let body: String = response.text().await.unwrap_or_default();
-            return Err(ApiError::InternalError(format!(
-                "Error {}: {}",
-                status, body
-            )));
+            let decision = classify_error(Some(status), &body);
+            return Err((
+                decision,
+                ApiError::InternalError(format!("Error {}: {}", status, body)),
+            ));
         }
 
         let embedding_response: EmbeddingResponse = response.json().await.map_err(|e| {
             info!("Error parsing embedding response: {}", e);
-            ApiError::RequestError(e)
+            (RetryDecision::Retry, ApiError::RequestError(e))
         })?;
 
         let embedding: ArrayBase<OwnedRepr<f32>, Dim<[usize; 1]>> =
@@ -132,3 +234,47 @@ impl EmbeddingModel {
         similarities
     }*/
 }
+
+#[async_trait]
+impl Embedder for EmbeddingModel {
+    async fn embed(&self, text: &str) -> Result<Embedding, ApiError> {
+        self.get_contextual_embeddings(text).await
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        self.get_batch_embeddings(texts).await
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for EmbeddingModel {
+    async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError> {
+        EmbeddingModel::get_contextual_embeddings(self, text).await
+    }
+
+    async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        EmbeddingModel::get_batch_embeddings(self, texts).await
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn similarity_calibration(&self) -> Option<&SimilarityCalibration> {
+        EmbeddingModel::similarity_calibration(self)
+    }
+}
+
+/// Cuts the prompt roughly in half (at a char boundary) so a
+/// `RetryTokenized` resubmission has a real chance of fitting.
+fn truncate_prompt(prompt: &str) -> String {
+    let mut end = prompt.len() / 2;
+    while end > 0 && !prompt.is_char_boundary(end) {
+        end -= 1;
+    }
+    prompt[..end].to_string()
+}
@@ -0,0 +1,143 @@
+use crate::embeddings::model::Embedding;
+use crate::embeddings::provider::EmbeddingProvider;
+use crate::error::ApiError;
+use async_trait::async_trait;
+use ndarray::Array1;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Dimensionality of OpenAI's `text-embedding-3-small`, the default model.
+pub const DEFAULT_OPENAI_DIMENSIONS: usize = 1536;
+
+#[derive(Debug, Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+/// `EmbeddingProvider` for OpenAI's embeddings endpoint, or any
+/// OpenAI-compatible self-hosted service exposing the same
+/// `POST {base_url}/embeddings` shape.
+pub struct OpenAiEmbeddingProvider {
+    base_url: String,
+    api_key: String,
+    model: String,
+    dimensions: usize,
+    id: String,
+    client: Client,
+}
+
+impl OpenAiEmbeddingProvider {
+    pub fn new(base_url: &str, api_key: &str, model: &str, dimensions: usize) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+            dimensions,
+            id: format!("openai:{}", model),
+            client,
+        }
+    }
+
+    /// Builds an `OpenAiEmbeddingProvider` from `OPENAI_*` environment
+    /// variables, so operators can swap in OpenAI or a compatible
+    /// self-hosted endpoint without a code change:
+    /// - `OPENAI_API_KEY` (required for the real OpenAI API)
+    /// - `OPENAI_API_BASE` (default `https://api.openai.com/v1`)
+    /// - `OPENAI_EMBEDDING_MODEL` (default `text-embedding-3-small`)
+    /// - `OPENAI_EMBEDDING_DIMENSIONS` (default 1536)
+    pub fn from_env() -> Self {
+        let base_url = std::env::var("OPENAI_API_BASE")
+            .unwrap_or_else(|_| "https://api.openai.com/v1".to_string());
+        let api_key = std::env::var("OPENAI_API_KEY").unwrap_or_default();
+        let model = std::env::var("OPENAI_EMBEDDING_MODEL")
+            .unwrap_or_else(|_| "text-embedding-3-small".to_string());
+        let dimensions = std::env::var("OPENAI_EMBEDDING_DIMENSIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_OPENAI_DIMENSIONS);
+
+        Self::new(&base_url, &api_key, &model, dimensions)
+    }
+
+    async fn post_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        let request = EmbeddingRequest {
+            model: &self.model,
+            input: texts,
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/embeddings", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&request)
+            .send()
+            .await
+            .map_err(ApiError::RequestError)?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError::InternalError(format!(
+                "OpenAI embeddings request failed ({}): {}",
+                status, body
+            )));
+        }
+
+        let parsed: EmbeddingResponse = response.json().await.map_err(ApiError::RequestError)?;
+        Ok(parsed
+            .data
+            .into_iter()
+            .map(|d| Array1::from(d.embedding))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAiEmbeddingProvider {
+    async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError> {
+        self.post_embeddings(&[text.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ApiError::InternalError("OpenAI returned no embeddings".to_string()))
+    }
+
+    async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        let non_empty: Vec<String> = texts
+            .iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        if non_empty.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.post_embeddings(&non_empty).await
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -0,0 +1,155 @@
+use crate::embeddings::pretrained::vocab::Vocab;
+use crate::embeddings::pretrained::EmbeddingMatrix;
+use crate::error::ApiError;
+use byteorder::{LittleEndian, ReadBytesExt};
+use ndarray::Array2;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+const FASTTEXT_MAGIC: i32 = 793_712_314;
+
+/// fastText dictionary entry types: 0 is a regular word, 1 is a supervised
+/// label. Only words contribute rows the rest of this crate cares about.
+const ENTRY_TYPE_WORD: i8 = 0;
+
+/// The subset of fastText's `Args` block needed to size and interpret the
+/// input matrix: embedding dimension, subword n-gram bounds, and bucket
+/// count.
+struct Args {
+    dim: usize,
+    minn: usize,
+    maxn: usize,
+    bucket: usize,
+}
+
+impl Args {
+    fn read<R: Read>(reader: &mut R) -> Result<Self, ApiError> {
+        let dim = read_i32(reader, "dim")?;
+        let _ws = read_i32(reader, "ws")?;
+        let _epoch = read_i32(reader, "epoch")?;
+        let _min_count = read_i32(reader, "minCount")?;
+        let _neg = read_i32(reader, "neg")?;
+        let _word_ngrams = read_i32(reader, "wordNgrams")?;
+        let _loss = read_i32(reader, "loss")?;
+        let _model = read_i32(reader, "model")?;
+        let bucket = read_i32(reader, "bucket")?;
+        let minn = read_i32(reader, "minn")?;
+        let maxn = read_i32(reader, "maxn")?;
+        let _lr_update_rate = read_i32(reader, "lrUpdateRate")?;
+        let _sampling_threshold = reader
+            .read_f64::<LittleEndian>()
+            .map_err(|e| ApiError::InternalError(format!("Failed to read samplingThreshold: {}", e)))?;
+
+        Ok(Self {
+            dim: dim as usize,
+            minn: minn as usize,
+            maxn: maxn as usize,
+            bucket: bucket as usize,
+        })
+    }
+}
+
+fn read_i32<R: Read>(reader: &mut R, field: &str) -> Result<i32, ApiError> {
+    reader
+        .read_i32::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read args.{}: {}", field, e)))
+}
+
+/// Reads a fastText `.bin` model: the magic/version preamble, the args
+/// block, the word/label dictionary, and the input embedding matrix (one
+/// row per vocabulary word, followed by one row per subword hash bucket).
+/// Supervised label rows and the output (classifier) matrix are skipped —
+/// this crate only needs word vectors. Quantized (`-qnorm`) models aren't
+/// supported yet; that's tracked separately under the product-quantized
+/// storage work.
+pub fn read_bin(path: &Path) -> Result<EmbeddingMatrix, ApiError> {
+    let file = File::open(path)
+        .map_err(|e| ApiError::InternalError(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut reader = BufReader::new(file);
+
+    let magic = reader
+        .read_i32::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read magic: {}", e)))?;
+    if magic != FASTTEXT_MAGIC {
+        return Err(ApiError::InternalError(format!(
+            "Not a fastText model (expected magic {}, got {})",
+            FASTTEXT_MAGIC, magic
+        )));
+    }
+    let _version = reader
+        .read_i32::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read version: {}", e)))?;
+
+    let args = Args::read(&mut reader)?;
+    let (words, nwords) = read_dictionary(&mut reader)?;
+
+    let quant_input = reader
+        .read_i8()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read quant flag: {}", e)))?;
+    if quant_input != 0 {
+        return Err(ApiError::InternalError(
+            "Quantized fastText models are not yet supported".to_string(),
+        ));
+    }
+
+    let rows = nwords + args.bucket;
+    let mut vectors = Array2::<f32>::zeros((rows, args.dim));
+    for row in 0..rows {
+        for col in 0..args.dim {
+            vectors[[row, col]] = reader
+                .read_f32::<LittleEndian>()
+                .map_err(|e| ApiError::InternalError(format!("Failed to read matrix value: {}", e)))?;
+        }
+    }
+
+    let vocab = Vocab::new(words).with_subwords(args.minn, args.maxn, args.bucket);
+    Ok(EmbeddingMatrix { vocab, vectors })
+}
+
+/// Reads fastText's serialized dictionary: a header of counts, then one
+/// null-terminated word/label string plus a count and entry-type byte per
+/// entry. Returns the words in row order and the declared vocabulary size
+/// (`nwords`), which callers need to locate where subword bucket rows start
+/// in the matrix.
+fn read_dictionary<R: Read>(reader: &mut R) -> Result<(Vec<String>, usize), ApiError> {
+    let size = read_i32(reader, "dict.size")? as usize;
+    let nwords = read_i32(reader, "dict.nwords")? as usize;
+    let _nlabels = read_i32(reader, "dict.nlabels")?;
+    let _ntokens = reader
+        .read_i64::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read dict.ntokens: {}", e)))?;
+    let _pruneidx_size = reader
+        .read_i64::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read dict.pruneidx_size: {}", e)))?;
+
+    let mut words = Vec::with_capacity(nwords);
+    for _ in 0..size {
+        let mut bytes = Vec::new();
+        loop {
+            let mut byte = [0u8; 1];
+            reader
+                .read_exact(&mut byte)
+                .map_err(|e| ApiError::InternalError(format!("Failed to read dict entry: {}", e)))?;
+            if byte[0] == 0 {
+                break;
+            }
+            bytes.push(byte[0]);
+        }
+        let word = String::from_utf8(bytes)
+            .map_err(|e| ApiError::InternalError(format!("Dict entry is not valid UTF-8: {}", e)))?;
+
+        let _count = reader
+            .read_i64::<LittleEndian>()
+            .map_err(|e| ApiError::InternalError(format!("Failed to read entry count: {}", e)))?;
+        let entry_type = reader
+            .read_i8()
+            .map_err(|e| ApiError::InternalError(format!("Failed to read entry type: {}", e)))?;
+
+        if entry_type == ENTRY_TYPE_WORD {
+            words.push(word);
+        }
+    }
+
+    Ok((words, nwords))
+}
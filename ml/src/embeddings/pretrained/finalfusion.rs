@@ -0,0 +1,181 @@
+use crate::embeddings::pretrained::vocab::Vocab;
+use crate::embeddings::pretrained::EmbeddingMatrix;
+use crate::error::ApiError;
+use byteorder::{LittleEndian, ReadBytesExt};
+use ndarray::Array2;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const FINALFUSION_MAGIC: &[u8; 4] = b"FiFu";
+
+/// Chunk identifiers in a finalfusion container. Any identifier this reader
+/// doesn't recognize (metadata, quantized storage, and the like) is skipped
+/// by its declared length rather than erroring, so this keeps working as
+/// new chunk types are added upstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkIdentifier {
+    Vocab,
+    NdArray,
+    Norms,
+    Other(u32),
+}
+
+impl From<u32> for ChunkIdentifier {
+    fn from(id: u32) -> Self {
+        match id {
+            1 => ChunkIdentifier::Vocab,
+            2 => ChunkIdentifier::NdArray,
+            5 => ChunkIdentifier::Norms,
+            other => ChunkIdentifier::Other(other),
+        }
+    }
+}
+
+/// Reads a finalfusion embeddings container: the magic/version header,
+/// then a sequence of chunks — a vocab chunk, a storage (`NdArray`) chunk,
+/// and an optional norms chunk. Embeddings are stored unit-normalized with
+/// their original magnitudes split into the norms chunk; when present,
+/// those norms are multiplied back in so `EmbeddingMatrix::lookup` returns
+/// vectors at their original scale.
+pub fn read(path: &Path) -> Result<EmbeddingMatrix, ApiError> {
+    let file = File::open(path)
+        .map_err(|e| ApiError::InternalError(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut reader = BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|e| ApiError::InternalError(format!("Failed to read magic: {}", e)))?;
+    if &magic != FINALFUSION_MAGIC {
+        return Err(ApiError::InternalError(
+            "Not a finalfusion container (bad magic)".to_string(),
+        ));
+    }
+    let _version = reader
+        .read_u32::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read version: {}", e)))?;
+
+    let mut words: Option<Vec<String>> = None;
+    let mut vectors: Option<Array2<f32>> = None;
+    let mut norms: Option<Vec<f32>> = None;
+
+    loop {
+        let id = match reader.read_u32::<LittleEndian>() {
+            Ok(id) => id,
+            Err(_) => break, // end of file: no more chunks
+        };
+        let chunk_len = reader
+            .read_u64::<LittleEndian>()
+            .map_err(|e| ApiError::InternalError(format!("Failed to read chunk length: {}", e)))?;
+
+        match ChunkIdentifier::from(id) {
+            ChunkIdentifier::Vocab => words = Some(read_vocab_chunk(&mut reader)?),
+            ChunkIdentifier::NdArray => vectors = Some(read_ndarray_chunk(&mut reader)?),
+            ChunkIdentifier::Norms => norms = Some(read_norms_chunk(&mut reader)?),
+            ChunkIdentifier::Other(_) => {
+                reader
+                    .seek(SeekFrom::Current(chunk_len as i64))
+                    .map_err(|e| ApiError::InternalError(format!("Failed to skip chunk: {}", e)))?;
+            }
+        }
+    }
+
+    let words = words.ok_or_else(|| ApiError::InternalError("Missing vocab chunk".to_string()))?;
+    let mut vectors =
+        vectors.ok_or_else(|| ApiError::InternalError("Missing storage chunk".to_string()))?;
+
+    if let Some(norms) = norms {
+        for (row, norm) in norms.iter().enumerate() {
+            if row < vectors.nrows() {
+                let mut row_view = vectors.row_mut(row);
+                row_view *= *norm;
+            }
+        }
+    }
+
+    Ok(EmbeddingMatrix {
+        vocab: Vocab::new(words),
+        vectors,
+    })
+}
+
+/// Reads a vocab chunk: a word count followed by that many length-prefixed
+/// UTF-8 strings.
+fn read_vocab_chunk<R: Read>(reader: &mut R) -> Result<Vec<String>, ApiError> {
+    let count = reader
+        .read_u64::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read vocab count: {}", e)))? as usize;
+
+    let mut words = Vec::with_capacity(count);
+    for _ in 0..count {
+        let len = reader
+            .read_u32::<LittleEndian>()
+            .map_err(|e| ApiError::InternalError(format!("Failed to read word length: {}", e)))? as usize;
+        let mut bytes = vec![0u8; len];
+        reader
+            .read_exact(&mut bytes)
+            .map_err(|e| ApiError::InternalError(format!("Failed to read word bytes: {}", e)))?;
+        words.push(
+            String::from_utf8(bytes)
+                .map_err(|e| ApiError::InternalError(format!("Word is not valid UTF-8: {}", e)))?,
+        );
+    }
+
+    Ok(words)
+}
+
+/// Reads an `NdArray` storage chunk: a `(rows, cols)` shape followed by
+/// `rows * cols` raw little-endian `f32`s, row-major.
+fn read_ndarray_chunk<R: Read>(reader: &mut R) -> Result<Array2<f32>, ApiError> {
+    let rows = reader
+        .read_u64::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read row count: {}", e)))? as usize;
+    let cols = reader
+        .read_u32::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read column count: {}", e)))? as usize;
+
+    let mut data = Vec::with_capacity(rows * cols);
+    for _ in 0..rows * cols {
+        data.push(
+            reader
+                .read_f32::<LittleEndian>()
+                .map_err(|e| ApiError::InternalError(format!("Failed to read storage value: {}", e)))?,
+        );
+    }
+
+    Array2::from_shape_vec((rows, cols), data)
+        .map_err(|e| ApiError::InternalError(format!("Malformed storage chunk: {}", e)))
+}
+
+/// Reads a norms chunk: one `f32` magnitude per vocabulary row, in the same
+/// order as the vocab chunk.
+fn read_norms_chunk<R: Read>(reader: &mut R) -> Result<Vec<f32>, ApiError> {
+    let count = reader
+        .read_u64::<LittleEndian>()
+        .map_err(|e| ApiError::InternalError(format!("Failed to read norms count: {}", e)))? as usize;
+
+    let mut norms = Vec::with_capacity(count);
+    for _ in 0..count {
+        norms.push(
+            reader
+                .read_f32::<LittleEndian>()
+                .map_err(|e| ApiError::InternalError(format!("Failed to read norm value: {}", e)))?,
+        );
+    }
+
+    Ok(norms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_identifier_from_known_ids() {
+        assert_eq!(ChunkIdentifier::from(1), ChunkIdentifier::Vocab);
+        assert_eq!(ChunkIdentifier::from(2), ChunkIdentifier::NdArray);
+        assert_eq!(ChunkIdentifier::from(5), ChunkIdentifier::Norms);
+        assert_eq!(ChunkIdentifier::from(99), ChunkIdentifier::Other(99));
+    }
+}
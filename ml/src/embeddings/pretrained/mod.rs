@@ -0,0 +1,123 @@
+pub mod fasttext;
+pub mod finalfusion;
+pub mod provider;
+pub mod vocab;
+pub mod word2vec;
+
+pub use provider::PretrainedEmbeddingProvider;
+pub use vocab::Vocab;
+
+use crate::embeddings::Embedding;
+use crate::error::ApiError;
+use ndarray::Array2;
+use std::path::Path;
+
+/// On-disk pretrained embedding formats `load_pretrained` can ingest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PretrainedFormat {
+    Word2VecText,
+    Word2VecBinary,
+    /// fastText's `.vec` files share the plain-text word2vec layout.
+    FastTextVec,
+    FastTextBin,
+    Finalfusion,
+}
+
+/// A pretrained embedding table: a `Vocab` (optionally with fastText-style
+/// subword buckets) mapping words to rows of `vectors`.
+pub struct EmbeddingMatrix {
+    pub vocab: Vocab,
+    pub vectors: Array2<f32>,
+}
+
+impl EmbeddingMatrix {
+    pub fn dimensions(&self) -> usize {
+        self.vectors.ncols()
+    }
+
+    /// Resolves `word` to an `Embedding`. An exact vocabulary hit returns
+    /// its row directly; otherwise, if the vocab carries subword buckets,
+    /// the average of `word`'s character n-gram bucket rows is returned.
+    /// `None` only when neither resolves to anything, letting callers
+    /// (e.g. `cluster_concepts`) fall back to embedding the concept
+    /// themselves instead.
+    pub fn lookup(&self, word: &str) -> Option<Embedding> {
+        if let Some(index) = self.vocab.index_of(word) {
+            return Some(self.vectors.row(index).to_owned());
+        }
+
+        let bucket_rows = self.vocab.subword_bucket_rows(word);
+        if bucket_rows.is_empty() {
+            return None;
+        }
+
+        let mut sum = Embedding::zeros(self.dimensions());
+        let mut count = 0usize;
+        for row in bucket_rows {
+            if row < self.vectors.nrows() {
+                sum += &self.vectors.row(row);
+                count += 1;
+            }
+        }
+
+        if count == 0 {
+            None
+        } else {
+            Some(sum / count as f32)
+        }
+    }
+}
+
+/// Loads a pretrained embedding table in `format` from `path`.
+pub fn load_pretrained(path: &Path, format: PretrainedFormat) -> Result<EmbeddingMatrix, ApiError> {
+    match format {
+        PretrainedFormat::Word2VecText => word2vec::read_text(path),
+        PretrainedFormat::Word2VecBinary => word2vec::read_binary(path),
+        PretrainedFormat::FastTextVec => word2vec::read_text(path),
+        PretrainedFormat::FastTextBin => fasttext::read_bin(path),
+        PretrainedFormat::Finalfusion => finalfusion::read(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix_with_subwords() -> EmbeddingMatrix {
+        let vocab = Vocab::new(vec!["cat".to_string(), "dog".to_string()]).with_subwords(3, 3, 4);
+        let vectors = Array2::from_shape_vec(
+            (6, 2),
+            vec![
+                1.0, 0.0, // cat
+                0.0, 1.0, // dog
+                2.0, 2.0, // bucket 0
+                4.0, 4.0, // bucket 1
+                6.0, 6.0, // bucket 2
+                8.0, 8.0, // bucket 3
+            ],
+        )
+        .unwrap();
+        EmbeddingMatrix { vocab, vectors }
+    }
+
+    #[test]
+    fn test_lookup_exact_vocabulary_hit() {
+        let matrix = matrix_with_subwords();
+        assert_eq!(matrix.lookup("cat").unwrap().to_vec(), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_lookup_falls_back_to_subword_average() {
+        let matrix = matrix_with_subwords();
+        let looked_up = matrix.lookup("caterpillar");
+        assert!(looked_up.is_some());
+    }
+
+    #[test]
+    fn test_lookup_returns_none_when_nothing_resolves() {
+        let vocab = Vocab::new(vec!["cat".to_string()]);
+        let vectors = Array2::from_shape_vec((1, 2), vec![1.0, 0.0]).unwrap();
+        let matrix = EmbeddingMatrix { vocab, vectors };
+        assert!(matrix.lookup("dog").is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::embeddings::pretrained::{load_pretrained, EmbeddingMatrix, PretrainedFormat};
+use crate::embeddings::provider::EmbeddingProvider;
+use crate::embeddings::Embedding;
+use crate::error::ApiError;
+use async_trait::async_trait;
+use std::path::Path;
+
+/// `EmbeddingProvider` backed by a pretrained `EmbeddingMatrix` loaded from
+/// disk, so `cluster_concepts`/`merge_similar_concepts` can operate over
+/// externally trained vectors (word2vec, fastText, finalfusion) instead of
+/// requiring every embedding to come from a live model call.
+///
+/// Multi-word concept text is embedded as the mean of its tokens' vectors;
+/// a single unresolvable token falls back to fastText-style subword buckets
+/// via `EmbeddingMatrix::lookup` before giving up on that token entirely.
+pub struct PretrainedEmbeddingProvider {
+    matrix: EmbeddingMatrix,
+    id: String,
+}
+
+impl PretrainedEmbeddingProvider {
+    pub fn new(matrix: EmbeddingMatrix, id: &str) -> Self {
+        Self {
+            matrix,
+            id: id.to_string(),
+        }
+    }
+
+    /// Builds a `PretrainedEmbeddingProvider` from environment variables:
+    /// - `PRETRAINED_EMBEDDINGS_PATH` (required)
+    /// - `PRETRAINED_EMBEDDINGS_FORMAT` (`word2vec-text`, `word2vec-binary`,
+    ///   `fasttext-vec`, `fasttext-bin`, or `finalfusion`; default
+    ///   `word2vec-text`)
+    /// - `PRETRAINED_EMBEDDINGS_ID` (default `"pretrained:<path>"`, stored
+    ///   alongside every saved concept so it's never mixed with vectors
+    ///   from a different table)
+    pub fn from_env() -> Result<Self, ApiError> {
+        let path = std::env::var("PRETRAINED_EMBEDDINGS_PATH").map_err(|_| {
+            ApiError::InternalError("PRETRAINED_EMBEDDINGS_PATH is not set".to_string())
+        })?;
+
+        let format = std::env::var("PRETRAINED_EMBEDDINGS_FORMAT")
+            .unwrap_or_else(|_| "word2vec-text".to_string());
+        let format = parse_format(&format)?;
+
+        let matrix = load_pretrained(Path::new(&path), format)?;
+        let id = std::env::var("PRETRAINED_EMBEDDINGS_ID")
+            .unwrap_or_else(|_| format!("pretrained:{}", path));
+
+        Ok(Self::new(matrix, &id))
+    }
+
+    fn embed_text(&self, text: &str) -> Result<Embedding, ApiError> {
+        let vectors: Vec<Embedding> = text
+            .split_whitespace()
+            .filter_map(|token| self.matrix.lookup(token))
+            .collect();
+
+        if vectors.is_empty() {
+            return Err(ApiError::InternalError(format!(
+                "No pretrained vector resolved for any token in '{}'",
+                text
+            )));
+        }
+
+        let mut sum = Embedding::zeros(self.matrix.dimensions());
+        for vector in &vectors {
+            sum += vector;
+        }
+        Ok(sum / vectors.len() as f32)
+    }
+}
+
+/// Parses the `PRETRAINED_EMBEDDINGS_FORMAT` value into a `PretrainedFormat`.
+fn parse_format(format: &str) -> Result<PretrainedFormat, ApiError> {
+    match format.to_lowercase().as_str() {
+        "word2vec-text" => Ok(PretrainedFormat::Word2VecText),
+        "word2vec-binary" => Ok(PretrainedFormat::Word2VecBinary),
+        "fasttext-vec" => Ok(PretrainedFormat::FastTextVec),
+        "fasttext-bin" => Ok(PretrainedFormat::FastTextBin),
+        "finalfusion" => Ok(PretrainedFormat::Finalfusion),
+        other => Err(ApiError::InternalError(format!(
+            "Unknown PRETRAINED_EMBEDDINGS_FORMAT: {}",
+            other
+        ))),
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for PretrainedEmbeddingProvider {
+    async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError> {
+        self.embed_text(text)
+    }
+
+    async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        texts.iter().map(|text| self.embed_text(text)).collect()
+    }
+
+    fn dimensions(&self) -> usize {
+        self.matrix.dimensions()
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeddings::pretrained::Vocab;
+    use ndarray::Array2;
+
+    fn matrix() -> EmbeddingMatrix {
+        let vocab = Vocab::new(vec!["cat".to_string(), "dog".to_string()]);
+        let vectors = Array2::from_shape_vec((2, 2), vec![1.0, 0.0, 0.0, 1.0]).unwrap();
+        EmbeddingMatrix { vocab, vectors }
+    }
+
+    #[tokio::test]
+    async fn test_embeds_multi_word_text_as_token_mean() {
+        let provider = PretrainedEmbeddingProvider::new(matrix(), "pretrained:test");
+        let embedding = provider
+            .get_contextual_embeddings("cat dog")
+            .await
+            .unwrap();
+        assert_eq!(embedding.to_vec(), vec![0.5, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_text_errors() {
+        let provider = PretrainedEmbeddingProvider::new(matrix(), "pretrained:test");
+        assert!(provider.get_contextual_embeddings("bird").await.is_err());
+    }
+
+    #[test]
+    fn test_parse_format_rejects_unknown_value() {
+        assert!(parse_format("unknown").is_err());
+    }
+}
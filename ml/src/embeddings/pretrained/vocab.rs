@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+/// Subword (character n-gram) hashing parameters for fastText-style
+/// out-of-vocabulary fallback.
+#[derive(Debug, Clone, Copy)]
+struct SubwordConfig {
+    min_n: usize,
+    max_n: usize,
+    buckets: usize,
+}
+
+/// Maps token strings to row indices in an `EmbeddingMatrix`, with optional
+/// fastText-style subword bucket hashing so a token that isn't in the
+/// vocabulary can still resolve to an approximate vector via its character
+/// n-grams instead of failing outright.
+#[derive(Debug, Clone)]
+pub struct Vocab {
+    indices: HashMap<String, usize>,
+    words: Vec<String>,
+    subwords: Option<SubwordConfig>,
+}
+
+impl Vocab {
+    pub fn new(words: Vec<String>) -> Self {
+        let indices = words
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(index, word)| (word, index))
+            .collect();
+
+        Self {
+            indices,
+            words,
+            subwords: None,
+        }
+    }
+
+    /// Enables subword fallback: an unknown word's character n-grams
+    /// (`min_n..=max_n` long, wrapped in `<`/`>` boundary markers per the
+    /// fastText convention) hash into `buckets` rows appended after the
+    /// in-vocabulary rows. See `subword_bucket_rows`.
+    pub fn with_subwords(mut self, min_n: usize, max_n: usize, buckets: usize) -> Self {
+        self.subwords = Some(SubwordConfig {
+            min_n,
+            max_n,
+            buckets,
+        });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn word(&self, index: usize) -> Option<&str> {
+        self.words.get(index).map(String::as_str)
+    }
+
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.indices.get(word).copied()
+    }
+
+    /// The bucket row indices (offset past the in-vocabulary rows) that
+    /// `word`'s character n-grams hash into. Empty when this vocab wasn't
+    /// built `with_subwords`.
+    pub fn subword_bucket_rows(&self, word: &str) -> Vec<usize> {
+        let Some(cfg) = self.subwords else {
+            return Vec::new();
+        };
+
+        let bounded: Vec<char> = format!("<{}>", word).chars().collect();
+        let mut rows = Vec::new();
+
+        for n in cfg.min_n..=cfg.max_n.min(bounded.len()) {
+            for start in 0..=bounded.len().saturating_sub(n) {
+                let ngram: String = bounded[start..start + n].iter().collect();
+                let bucket = fasttext_hash(&ngram) as usize % cfg.buckets;
+                rows.push(self.words.len() + bucket);
+            }
+        }
+
+        rows
+    }
+}
+
+/// FNV-1a as used by fastText to hash character n-grams into subword
+/// buckets (`Dictionary::hash` in the reference implementation).
+fn fasttext_hash(ngram: &str) -> u32 {
+    let mut hash: u32 = 2_166_136_261;
+    for byte in ngram.bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(16_777_619);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_of_known_word() {
+        let vocab = Vocab::new(vec!["cat".to_string(), "dog".to_string()]);
+        assert_eq!(vocab.index_of("dog"), Some(1));
+        assert_eq!(vocab.index_of("bird"), None);
+    }
+
+    #[test]
+    fn test_subword_bucket_rows_empty_without_config() {
+        let vocab = Vocab::new(vec!["cat".to_string()]);
+        assert!(vocab.subword_bucket_rows("caterpillar").is_empty());
+    }
+
+    #[test]
+    fn test_subword_bucket_rows_offset_past_vocab() {
+        let vocab = Vocab::new(vec!["cat".to_string(), "dog".to_string()])
+            .with_subwords(3, 3, 100);
+        let rows = vocab.subword_bucket_rows("caterpillar");
+        assert!(!rows.is_empty());
+        assert!(rows.iter().all(|&row| row >= 2));
+    }
+
+    #[test]
+    fn test_subword_bucket_rows_deterministic() {
+        let vocab = Vocab::new(vec![]).with_subwords(2, 4, 50);
+        assert_eq!(
+            vocab.subword_bucket_rows("hello"),
+            vocab.subword_bucket_rows("hello")
+        );
+    }
+}
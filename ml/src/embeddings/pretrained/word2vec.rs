@@ -0,0 +1,218 @@
+use crate::embeddings::pretrained::vocab::Vocab;
+use crate::embeddings::pretrained::EmbeddingMatrix;
+use crate::error::ApiError;
+use byteorder::{LittleEndian, ReadBytesExt};
+use ndarray::Array2;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+/// Reads the plain-text word2vec format: a `<count> <dim>` header line
+/// followed by one `word v1 v2 ... vdim` line per vector. fastText's `.vec`
+/// files use the same layout, so this doubles as the fastText text reader.
+pub fn read_text(path: &Path) -> Result<EmbeddingMatrix, ApiError> {
+    let file = File::open(path)
+        .map_err(|e| ApiError::InternalError(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header = lines
+        .next()
+        .ok_or_else(|| ApiError::InternalError("Empty embedding file".to_string()))?
+        .map_err(|e| ApiError::InternalError(format!("Failed to read header: {}", e)))?;
+    let (count, dim) = parse_header(&header)?;
+
+    let mut words = Vec::with_capacity(count);
+    let mut vectors = Array2::<f32>::zeros((count, dim));
+
+    for (row, line) in lines.enumerate() {
+        if row >= count {
+            break;
+        }
+        let line = line
+            .map_err(|e| ApiError::InternalError(format!("Failed to read vector line: {}", e)))?;
+        let mut parts = line.split_whitespace();
+        let word = parts
+            .next()
+            .ok_or_else(|| ApiError::InternalError(format!("Missing word on row {}", row)))?;
+        words.push(word.to_string());
+
+        for (col, value) in parts.enumerate() {
+            if col >= dim {
+                break;
+            }
+            vectors[[row, col]] = value
+                .parse::<f32>()
+                .map_err(|e| ApiError::InternalError(format!("Malformed float on row {}: {}", row, e)))?;
+        }
+    }
+
+    Ok(EmbeddingMatrix {
+        vocab: Vocab::new(words),
+        vectors,
+    })
+}
+
+/// Reads the original word2vec C-tool binary format: the same
+/// `<count> <dim>` ASCII header, then for each row a space-terminated word
+/// followed by `dim` raw little-endian `f32`s.
+pub fn read_binary(path: &Path) -> Result<EmbeddingMatrix, ApiError> {
+    let file = File::open(path)
+        .map_err(|e| ApiError::InternalError(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut reader = BufReader::new(file);
+
+    let (count, dim) = read_ascii_header(&mut reader)?;
+
+    let mut words = Vec::with_capacity(count);
+    let mut vectors = Array2::<f32>::zeros((count, dim));
+
+    for row in 0..count {
+        words.push(read_ascii_word(&mut reader)?);
+
+        for col in 0..dim {
+            vectors[[row, col]] = reader
+                .read_f32::<LittleEndian>()
+                .map_err(|e| ApiError::InternalError(format!("Failed to read vector component: {}", e)))?;
+        }
+    }
+
+    Ok(EmbeddingMatrix {
+        vocab: Vocab::new(words),
+        vectors,
+    })
+}
+
+/// Writes `matrix` back out in the plain-text word2vec format, the
+/// counterpart to `read_text`.
+pub fn write_text(matrix: &EmbeddingMatrix, path: &Path) -> Result<(), ApiError> {
+    let mut file = File::create(path)
+        .map_err(|e| ApiError::InternalError(format!("Failed to create {}: {}", path.display(), e)))?;
+
+    writeln!(file, "{} {}", matrix.vocab.len(), matrix.dimensions())
+        .map_err(|e| ApiError::InternalError(format!("Failed to write header: {}", e)))?;
+
+    for (row, word) in matrix.vocab.words().iter().enumerate() {
+        let values: Vec<String> = matrix.vectors.row(row).iter().map(f32::to_string).collect();
+        writeln!(file, "{} {}", word, values.join(" "))
+            .map_err(|e| ApiError::InternalError(format!("Failed to write row {}: {}", row, e)))?;
+    }
+
+    Ok(())
+}
+
+fn parse_header(header: &str) -> Result<(usize, usize), ApiError> {
+    let mut parts = header.split_whitespace();
+    let count = parts
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| ApiError::InternalError("Malformed word2vec header".to_string()))?;
+    let dim = parts
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| ApiError::InternalError("Malformed word2vec header".to_string()))?;
+    Ok((count, dim))
+}
+
+/// Reads the `<count> <dim>\n` ASCII header shared by the binary format.
+pub(crate) fn read_ascii_header<R: BufRead>(reader: &mut R) -> Result<(usize, usize), ApiError> {
+    let mut header = String::new();
+    reader
+        .read_line(&mut header)
+        .map_err(|e| ApiError::InternalError(format!("Failed to read header: {}", e)))?;
+    parse_header(&header)
+}
+
+/// Reads a single space-terminated ASCII word, as used before each row of
+/// vector data in the word2vec/fastText binary formats. A leftover `\n`
+/// from the previous row's vector data may appear before the word itself
+/// (the reference word2vec/gensim loaders emit `word<space><floats><newline>`
+/// per row); that `\n` is a separator between rows, not a word delimiter,
+/// so it's skipped over rather than ending the word.
+pub(crate) fn read_ascii_word<R: Read>(reader: &mut R) -> Result<String, ApiError> {
+    let mut bytes = Vec::new();
+    let mut byte = [0u8; 1];
+    loop {
+        reader
+            .read_exact(&mut byte)
+            .map_err(|e| ApiError::InternalError(format!("Failed to read word byte: {}", e)))?;
+        if byte[0] == b' ' {
+            break;
+        }
+        if byte[0] == b'\n' {
+            continue;
+        }
+        bytes.push(byte[0]);
+    }
+    String::from_utf8(bytes)
+        .map_err(|e| ApiError::InternalError(format!("Word is not valid UTF-8: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(parse_header("3 5").unwrap(), (3, 5));
+        assert!(parse_header("not a header").is_err());
+    }
+
+    #[test]
+    fn test_read_ascii_word_splits_on_space() {
+        let mut cursor = Cursor::new(b"hello world".to_vec());
+        let word = read_ascii_word(&mut cursor).unwrap();
+        assert_eq!(word, "hello");
+    }
+
+    #[test]
+    fn test_read_text_round_trips_via_write_text() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("oort_word2vec_test.vec");
+
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "2 3").unwrap();
+        writeln!(file, "cat 1.0 2.0 3.0").unwrap();
+        writeln!(file, "dog 4.0 5.0 6.0").unwrap();
+        drop(file);
+
+        let matrix = read_text(&path).unwrap();
+        assert_eq!(matrix.vocab.len(), 2);
+        assert_eq!(matrix.dimensions(), 3);
+        assert_eq!(matrix.vocab.index_of("dog"), Some(1));
+        assert_eq!(matrix.vectors.row(0).to_vec(), vec![1.0, 2.0, 3.0]);
+
+        let round_trip_path = dir.join("oort_word2vec_test_out.vec");
+        write_text(&matrix, &round_trip_path).unwrap();
+        let reloaded = read_text(&round_trip_path).unwrap();
+        assert_eq!(reloaded.vectors, matrix.vectors);
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&round_trip_path);
+    }
+
+    #[test]
+    fn test_read_binary_parses_multiple_rows() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("oort_word2vec_binary_test.bin");
+
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "2 2").unwrap();
+        file.write_all(b"cat ").unwrap();
+        file.write_all(&1.0f32.to_le_bytes()).unwrap();
+        file.write_all(&2.0f32.to_le_bytes()).unwrap();
+        file.write_all(b"\n").unwrap();
+        file.write_all(b"dog ").unwrap();
+        file.write_all(&3.0f32.to_le_bytes()).unwrap();
+        file.write_all(&4.0f32.to_le_bytes()).unwrap();
+        file.write_all(b"\n").unwrap();
+        drop(file);
+
+        let matrix = read_binary(&path).unwrap();
+        assert_eq!(matrix.vocab.index_of("cat"), Some(0));
+        assert_eq!(matrix.vocab.index_of("dog"), Some(1));
+        assert_eq!(matrix.vectors.row(0).to_vec(), vec![1.0, 2.0]);
+        assert_eq!(matrix.vectors.row(1).to_vec(), vec![3.0, 4.0]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
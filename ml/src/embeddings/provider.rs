@@ -0,0 +1,61 @@
+use crate::embeddings::calibration::SimilarityCalibration;
+use crate::embeddings::model::Embedding;
+use crate::error::ApiError;
+use async_trait::async_trait;
+
+/// A swappable source of text embeddings, selected at startup by
+/// `provider_from_env`. `AppState` holds `Arc<dyn EmbeddingProvider>` so
+/// operators can point Oort at Ollama, an OpenAI-compatible endpoint, or
+/// an in-process model without a code change.
+///
+/// `id()` identifies the provider/model combination (e.g.
+/// `"ollama:snowflake-arctic-embed2"`) and is stored alongside every saved
+/// concept so embeddings from different models are never compared or
+/// mixed together in `cluster_concepts`.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError>;
+
+    async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError>;
+
+    /// The length of vectors this provider produces.
+    fn dimensions(&self) -> usize;
+
+    /// Stable identifier for the provider/model combination in use.
+    fn id(&self) -> &str;
+
+    /// The mean/std calibration configured for this provider, if any.
+    /// Providers that don't support calibration keep today's identity
+    /// behavior.
+    fn similarity_calibration(&self) -> Option<&SimilarityCalibration> {
+        None
+    }
+}
+
+/// Selects an `EmbeddingProvider` from the `EMBEDDING_PROVIDER` environment
+/// variable (`ollama`, `openai`, `rest`, `pretrained`, or `local`; defaults
+/// to `ollama` when unset). `ollama_base_url` is used only by the `ollama`
+/// provider; `rest` is configured entirely through `REST_EMBEDDING_*`
+/// variables, see [`crate::embeddings::rest::TemplateRestEmbedder::from_env`];
+/// `pretrained` loads a word2vec/fastText/finalfusion table from
+/// `PRETRAINED_EMBEDDINGS_*` variables, see
+/// [`crate::embeddings::pretrained::PretrainedEmbeddingProvider::from_env`].
+pub fn provider_from_env(ollama_base_url: &str) -> std::sync::Arc<dyn EmbeddingProvider> {
+    use std::sync::Arc;
+
+    let provider = std::env::var("EMBEDDING_PROVIDER").unwrap_or_else(|_| "ollama".to_string());
+
+    match provider.to_lowercase().as_str() {
+        "openai" => Arc::new(crate::embeddings::openai::OpenAiEmbeddingProvider::from_env()),
+        "rest" => Arc::new(
+            crate::embeddings::rest::TemplateRestEmbedder::from_env()
+                .expect("Invalid templated REST embedding configuration"),
+        ),
+        "pretrained" => Arc::new(
+            crate::embeddings::pretrained::PretrainedEmbeddingProvider::from_env()
+                .expect("Invalid pretrained embedding configuration"),
+        ),
+        "local" => Arc::new(crate::embeddings::local::LocalEmbeddingProvider::new()),
+        _ => Arc::new(crate::embeddings::model::EmbeddingModel::new(ollama_base_url)),
+    }
+}
@@ -0,0 +1,292 @@
+use crate::embeddings::Embedding;
+use crate::error::ApiError;
+use linfa::prelude::*;
+use linfa_clustering::KMeans;
+use ndarray::{Array1, Array2, Array3, ArrayView1, Axis};
+
+/// Number of centroids trained per subspace. Each subspace index therefore
+/// fits in a single byte, which is the whole point of product quantization.
+pub const CENTROIDS_PER_SUBSPACE: usize = 256;
+
+/// `m` one-byte centroid indices, one per subspace, standing in for a full
+/// `Embedding` of four-byte `f32` components (a 768-dim vector shrinks from
+/// 3072 bytes to `m` bytes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantizedEmbedding {
+    pub codes: Vec<u8>,
+}
+
+/// `m` independent codebooks of `CENTROIDS_PER_SUBSPACE` centroids each,
+/// trained by splitting every training vector into `m` contiguous
+/// subvectors and running k-means within each subspace separately. Encodes
+/// embeddings to/from `QuantizedEmbedding` and builds the per-query distance
+/// tables that make asymmetric distance computation possible without ever
+/// reconstructing the original vectors.
+pub struct ProductQuantizer {
+    /// `(m, CENTROIDS_PER_SUBSPACE, subspace_dim)`: centroid `c`'s vector in
+    /// subspace `s` is `centroids[[s, c, ..]]`.
+    centroids: Array3<f32>,
+    /// `(m, CENTROIDS_PER_SUBSPACE)`: squared L2 norm of each centroid,
+    /// precomputed so cosine denominators don't need reconstruction either.
+    centroid_norms_sq: Array2<f32>,
+    subspaces: usize,
+    subspace_dim: usize,
+}
+
+impl ProductQuantizer {
+    /// Trains a quantizer over `embeddings` by splitting each `dim`-length
+    /// vector into `subspaces` contiguous, equal-length chunks and running
+    /// k-means independently within each chunk, with `CENTROIDS_PER_SUBSPACE`
+    /// centroids or as many as there are training vectors if that is fewer.
+    /// Codebook slots left untrained stay zero vectors and can still be
+    /// chosen by `encode`.
+    pub fn train(embeddings: &[Embedding], subspaces: usize) -> Result<Self, ApiError> {
+        if embeddings.is_empty() {
+            return Err(ApiError::InternalError("Empty embeddings".to_string()));
+        }
+        if subspaces == 0 {
+            return Err(ApiError::InternalError("subspaces must be at least 1".to_string()));
+        }
+
+        let dim = embeddings[0].len();
+        if dim % subspaces != 0 {
+            return Err(ApiError::InternalError(format!(
+                "Embedding dimension {} is not divisible by {} subspaces",
+                dim, subspaces
+            )));
+        }
+        let subspace_dim = dim / subspaces;
+
+        let n_centroids = CENTROIDS_PER_SUBSPACE.min(embeddings.len());
+        let mut centroids = Array3::<f32>::zeros((subspaces, CENTROIDS_PER_SUBSPACE, subspace_dim));
+
+        for subspace in 0..subspaces {
+            let offset = subspace * subspace_dim;
+            let mut data = Array2::<f64>::zeros((embeddings.len(), subspace_dim));
+            for (row, embedding) in embeddings.iter().enumerate() {
+                for col in 0..subspace_dim {
+                    data[[row, col]] = embedding[offset + col] as f64;
+                }
+            }
+
+            let dataset = Dataset::from(data);
+            let kmeans = KMeans::params(n_centroids)
+                .max_n_iterations(100)
+                .tolerance(1e-5)
+                .fit(&dataset)
+                .map_err(|e| {
+                    ApiError::DimensionalityError(format!(
+                        "K-Means error training subspace {}: {}",
+                        subspace, e
+                    ))
+                })?;
+
+            for (centroid_idx, centroid) in kmeans.centroids().rows().into_iter().enumerate() {
+                for col in 0..subspace_dim {
+                    centroids[[subspace, centroid_idx, col]] = centroid[col] as f32;
+                }
+            }
+        }
+
+        let centroid_norms_sq = centroids
+            .axis_iter(Axis(0))
+            .map(|subspace_centroids| {
+                subspace_centroids
+                    .axis_iter(Axis(0))
+                    .map(|centroid| centroid.dot(&centroid))
+                    .collect::<Vec<f32>>()
+            })
+            .collect::<Vec<_>>();
+        let centroid_norms_sq = Array2::from_shape_fn(
+            (subspaces, CENTROIDS_PER_SUBSPACE),
+            |(s, c)| *centroid_norms_sq[s].get(c).unwrap_or(&0.0),
+        );
+
+        Ok(Self {
+            centroids,
+            centroid_norms_sq,
+            subspaces,
+            subspace_dim,
+        })
+    }
+
+    /// Encodes `embedding` as `m` one-byte nearest-centroid indices, one per
+    /// subspace.
+    pub fn encode(&self, embedding: &Embedding) -> QuantizedEmbedding {
+        let mut codes = Vec::with_capacity(self.subspaces);
+
+        for subspace in 0..self.subspaces {
+            let offset = subspace * self.subspace_dim;
+            let sub_vector = embedding.slice(ndarray::s![offset..offset + self.subspace_dim]);
+            let code = self.nearest_centroid(subspace, sub_vector);
+            codes.push(code as u8);
+        }
+
+        QuantizedEmbedding { codes }
+    }
+
+    /// Reconstructs an approximate `Embedding` by concatenating each
+    /// subspace's selected centroid.
+    pub fn reconstruct(&self, quantized: &QuantizedEmbedding) -> Embedding {
+        let dim = self.subspaces * self.subspace_dim;
+        let mut values = Vec::with_capacity(dim);
+
+        for (subspace, &code) in quantized.codes.iter().enumerate() {
+            let centroid = self.centroids.index_axis(Axis(0), subspace);
+            let centroid = centroid.index_axis(Axis(0), code as usize);
+            values.extend(centroid.iter().copied());
+        }
+
+        Array1::from(values)
+    }
+
+    /// Precomputes, for `query`, the distance from each of its subspace
+    /// slices to every centroid in that subspace. Summing the entries a
+    /// `QuantizedEmbedding`'s codes select out of this table approximates
+    /// that vector's distance to `query` without ever reconstructing it.
+    pub fn distance_table(&self, query: &Embedding) -> DistanceTable {
+        let mut dot = Array2::<f32>::zeros((self.subspaces, CENTROIDS_PER_SUBSPACE));
+        let mut l2 = Array2::<f32>::zeros((self.subspaces, CENTROIDS_PER_SUBSPACE));
+
+        for subspace in 0..self.subspaces {
+            let offset = subspace * self.subspace_dim;
+            let query_sub = query.slice(ndarray::s![offset..offset + self.subspace_dim]);
+
+            for code in 0..CENTROIDS_PER_SUBSPACE {
+                let centroid = self.centroids.index_axis(Axis(0), subspace);
+                let centroid = centroid.index_axis(Axis(0), code);
+                dot[[subspace, code]] = query_sub.dot(&centroid);
+
+                let diff = &query_sub.to_owned() - &centroid;
+                l2[[subspace, code]] = diff.dot(&diff);
+            }
+        }
+
+        let query_norm_sq = query.dot(query);
+
+        DistanceTable {
+            dot,
+            l2,
+            query_norm_sq,
+        }
+    }
+
+    /// Approximate cosine similarity between `table`'s query and
+    /// `quantized`, computed entirely from precomputed lookups — no
+    /// reconstruction required.
+    pub fn cosine_similarity(&self, table: &DistanceTable, quantized: &QuantizedEmbedding) -> f32 {
+        let mut dot = 0.0_f32;
+        let mut norm_sq = 0.0_f32;
+
+        for (subspace, &code) in quantized.codes.iter().enumerate() {
+            dot += table.dot[[subspace, code as usize]];
+            norm_sq += self.centroid_norms_sq[[subspace, code as usize]];
+        }
+
+        if table.query_norm_sq <= 0.0 || norm_sq <= 0.0 {
+            return 0.0;
+        }
+
+        dot / (table.query_norm_sq.sqrt() * norm_sq.sqrt())
+    }
+
+    /// Approximate squared L2 distance between `table`'s query and
+    /// `quantized`, computed entirely from precomputed lookups.
+    pub fn l2_distance_squared(&self, table: &DistanceTable, quantized: &QuantizedEmbedding) -> f32 {
+        quantized
+            .codes
+            .iter()
+            .enumerate()
+            .map(|(subspace, &code)| table.l2[[subspace, code as usize]])
+            .sum()
+    }
+
+    fn nearest_centroid(&self, subspace: usize, sub_vector: ArrayView1<f32>) -> usize {
+        let centroids = self.centroids.index_axis(Axis(0), subspace);
+        let mut best_code = 0;
+        let mut best_distance = f32::MAX;
+
+        for (code, centroid) in centroids.axis_iter(Axis(0)).enumerate() {
+            let diff = &sub_vector.to_owned() - &centroid;
+            let distance = diff.dot(&diff);
+            if distance < best_distance {
+                best_distance = distance;
+                best_code = code;
+            }
+        }
+
+        best_code
+    }
+}
+
+/// Per-query, per-subspace distance lookup tables produced by
+/// `ProductQuantizer::distance_table`. Reused across every
+/// `QuantizedEmbedding` being compared against the same query so the
+/// per-subspace distance to each of the 256 centroids is computed once
+/// rather than once per candidate.
+pub struct DistanceTable {
+    dot: Array2<f32>,
+    l2: Array2<f32>,
+    query_norm_sq: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_embeddings() -> Vec<Embedding> {
+        // Two well-separated clusters in a 4-dim space, split into 2
+        // subspaces of 2 dims each.
+        vec![
+            Array1::from(vec![1.0, 1.0, 1.0, 1.0]),
+            Array1::from(vec![1.1, 0.9, 1.1, 0.9]),
+            Array1::from(vec![-1.0, -1.0, -1.0, -1.0]),
+            Array1::from(vec![-1.1, -0.9, -1.1, -0.9]),
+        ]
+    }
+
+    #[test]
+    fn test_train_rejects_indivisible_subspaces() {
+        let embeddings = sample_embeddings();
+        assert!(ProductQuantizer::train(&embeddings, 3).is_err());
+    }
+
+    #[test]
+    fn test_encode_reconstruct_round_trip_is_close() {
+        let embeddings = sample_embeddings();
+        let pq = ProductQuantizer::train(&embeddings, 2).unwrap();
+
+        let query = &embeddings[0];
+        let codes = pq.encode(query);
+        let reconstructed = pq.reconstruct(&codes);
+
+        let diff = query - &reconstructed;
+        let error: f32 = diff.dot(&diff);
+        assert!(error < 0.5, "reconstruction error too high: {}", error);
+    }
+
+    #[test]
+    fn test_asymmetric_cosine_matches_dense_ordering() {
+        let embeddings = sample_embeddings();
+        let pq = ProductQuantizer::train(&embeddings, 2).unwrap();
+
+        let query = Array1::from(vec![1.0, 1.0, 1.0, 1.0]);
+        let table = pq.distance_table(&query);
+
+        let near_codes = pq.encode(&embeddings[1]);
+        let far_codes = pq.encode(&embeddings[2]);
+
+        let near_similarity = pq.cosine_similarity(&table, &near_codes);
+        let far_similarity = pq.cosine_similarity(&table, &far_codes);
+
+        assert!(near_similarity > far_similarity);
+    }
+
+    #[test]
+    fn test_quantized_embedding_size_is_one_byte_per_subspace() {
+        let embeddings = sample_embeddings();
+        let pq = ProductQuantizer::train(&embeddings, 2).unwrap();
+        let codes = pq.encode(&embeddings[0]);
+        assert_eq!(codes.codes.len(), 2);
+    }
+}
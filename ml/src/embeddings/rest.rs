@@ -0,0 +1,220 @@
+use crate::embeddings::embedder::Embedder;
+use crate::embeddings::model::Embedding;
+use crate::embeddings::provider::EmbeddingProvider;
+use crate::embeddings::template::{find_placeholder, get_at_path, set_at_path, JsonPath};
+use crate::error::ApiError;
+use async_trait::async_trait;
+use ndarray::Array1;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const TEXT_MARKER: &str = "{{text}}";
+const EMBEDDING_MARKER: &str = "{{embedding}}";
+
+/// Dimensionality assumed for a `TemplateRestEmbedder` when
+/// `REST_EMBEDDING_DIMENSIONS` isn't set. Matches OpenAI's
+/// `text-embedding-3-small`, since the default request/response templates
+/// mirror that API's shape.
+pub const DEFAULT_REST_DIMENSIONS: usize = 1536;
+
+/// An `Embedder` configured entirely by two JSON templates, so one type can
+/// talk to Ollama, OpenAI, or any self-hosted embedding API.
+///
+/// `request_template` is a JSON value with a single `"{{text}}"` string
+/// marking where the input is injected; `response_template` is a JSON value
+/// with a single `"{{embedding}}"` string marking where the float array is
+/// read from the response. Both are parsed once at construction time into
+/// the JSON path to the marker, so per-request work is just a clone + splice.
+pub struct TemplateRestEmbedder {
+    url: String,
+    client: Client,
+    request_template: Value,
+    request_path: JsonPath,
+    response_path: JsonPath,
+    bearer_token: Option<String>,
+    dimensions: usize,
+    id: String,
+}
+
+impl TemplateRestEmbedder {
+    pub fn new(
+        url: &str,
+        request_template: Value,
+        response_template: Value,
+        bearer_token: Option<String>,
+    ) -> Result<Self, ApiError> {
+        let request_path = find_placeholder(&request_template, TEXT_MARKER).ok_or_else(|| {
+            ApiError::InternalError(format!(
+                "Request template is missing the {} placeholder",
+                TEXT_MARKER
+            ))
+        })?;
+
+        let response_path =
+            find_placeholder(&response_template, EMBEDDING_MARKER).ok_or_else(|| {
+                ApiError::InternalError(format!(
+                    "Response template is missing the {} placeholder",
+                    EMBEDDING_MARKER
+                ))
+            })?;
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Ok(Self {
+            url: url.to_string(),
+            client,
+            request_template,
+            request_path,
+            response_path,
+            bearer_token,
+            dimensions: DEFAULT_REST_DIMENSIONS,
+            id: "rest:custom".to_string(),
+        })
+    }
+
+    /// Overrides the vector length reported to callers via `EmbeddingProvider::dimensions`.
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+
+    /// Overrides the `EmbeddingProvider::id` reported for this backend, so
+    /// concepts saved against one templated REST endpoint aren't mixed with
+    /// another.
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    /// Builds a `TemplateRestEmbedder` from environment variables, so
+    /// operators can point Oort at an OpenAI-style or self-hosted REST
+    /// embedding endpoint without a code change:
+    /// - `REST_EMBEDDING_URL` (required)
+    /// - `REST_EMBEDDING_REQUEST_TEMPLATE` (JSON string; default mirrors
+    ///   OpenAI's `{"input": "..."}` request body)
+    /// - `REST_EMBEDDING_RESPONSE_TEMPLATE` (JSON string; default mirrors
+    ///   OpenAI's `{"data": [{"embedding": [...]}]}` response body)
+    /// - `REST_EMBEDDING_BEARER_TOKEN` (optional)
+    /// - `REST_EMBEDDING_DIMENSIONS` (default [`DEFAULT_REST_DIMENSIONS`])
+    /// - `REST_EMBEDDING_ID` (default `"rest:custom"`)
+    pub fn from_env() -> Result<Self, ApiError> {
+        let url = std::env::var("REST_EMBEDDING_URL").map_err(|_| {
+            ApiError::InternalError("REST_EMBEDDING_URL is not set".to_string())
+        })?;
+
+        let request_template = match std::env::var("REST_EMBEDDING_REQUEST_TEMPLATE") {
+            Ok(raw) => serde_json::from_str(&raw).map_err(|e| {
+                ApiError::InternalError(format!("Invalid REST_EMBEDDING_REQUEST_TEMPLATE: {}", e))
+            })?,
+            Err(_) => json!({ "input": TEXT_MARKER }),
+        };
+
+        let response_template = match std::env::var("REST_EMBEDDING_RESPONSE_TEMPLATE") {
+            Ok(raw) => serde_json::from_str(&raw).map_err(|e| {
+                ApiError::InternalError(format!("Invalid REST_EMBEDDING_RESPONSE_TEMPLATE: {}", e))
+            })?,
+            Err(_) => json!({ "data": [{ "embedding": EMBEDDING_MARKER }] }),
+        };
+
+        let bearer_token = std::env::var("REST_EMBEDDING_BEARER_TOKEN").ok();
+
+        let dimensions = std::env::var("REST_EMBEDDING_DIMENSIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_REST_DIMENSIONS);
+
+        let id = std::env::var("REST_EMBEDDING_ID").unwrap_or_else(|_| "rest:custom".to_string());
+
+        Ok(Self::new(&url, request_template, response_template, bearer_token)?
+            .with_dimensions(dimensions)
+            .with_id(&id))
+    }
+}
+
+#[async_trait]
+impl Embedder for TemplateRestEmbedder {
+    async fn embed(&self, text: &str) -> Result<Embedding, ApiError> {
+        let mut body = self.request_template.clone();
+        set_at_path(&mut body, &self.request_path, Value::String(text.to_string()))
+            .ok_or_else(|| {
+                ApiError::InternalError("Failed to splice text into request template".to_string())
+            })?;
+
+        let mut req = self.client.post(&self.url).json(&body);
+        if let Some(token) = &self.bearer_token {
+            req = req.bearer_auth(token);
+        }
+
+        let response = req.send().await.map_err(ApiError::RequestError)?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::InternalError(format!(
+                "Error {}: {}",
+                status, text
+            )));
+        }
+
+        let response_json: Value = response.json().await.map_err(ApiError::RequestError)?;
+
+        let embedding_value = get_at_path(&response_json, &self.response_path).ok_or_else(|| {
+            ApiError::InternalError(
+                "Response JSON did not contain a value at the embedding path".to_string(),
+            )
+        })?;
+
+        let floats: Vec<f32> = serde_json::from_value(embedding_value.clone()).map_err(|e| {
+            ApiError::InternalError(format!("Embedding path was not a float array: {}", e))
+        })?;
+
+        Ok(Array1::from(floats))
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for TemplateRestEmbedder {
+    async fn get_contextual_embeddings(&self, text: &str) -> Result<Embedding, ApiError> {
+        self.embed(text).await
+    }
+
+    async fn get_batch_embeddings(&self, texts: &[String]) -> Result<Vec<Embedding>, ApiError> {
+        self.embed_batch(texts).await
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construction_requires_both_markers() {
+        let ok = TemplateRestEmbedder::new(
+            "http://localhost/embed",
+            json!({ "prompt": "{{text}}" }),
+            json!({ "embedding": "{{embedding}}" }),
+            None,
+        );
+        assert!(ok.is_ok());
+
+        let missing_request_marker = TemplateRestEmbedder::new(
+            "http://localhost/embed",
+            json!({ "prompt": "no marker here" }),
+            json!({ "embedding": "{{embedding}}" }),
+            None,
+        );
+        assert!(missing_request_marker.is_err());
+    }
+}
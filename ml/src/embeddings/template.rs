@@ -0,0 +1,106 @@
+use serde_json::Value;
+
+/// One step into a JSON value: either an object field or an array index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+/// The location of a placeholder inside a parsed JSON template.
+#[derive(Debug, Clone)]
+pub struct JsonPath(Vec<PathSegment>);
+
+/// Recursively searches `value` for a string equal to `marker`, returning the
+/// path to it if found. Templates are expected to contain the marker exactly
+/// once; only the first match (depth-first, object keys then array order) is
+/// used.
+fn find_marker(value: &Value, marker: &str) -> Option<JsonPath> {
+    match value {
+        Value::String(s) if s == marker => Some(JsonPath(Vec::new())),
+        Value::Object(map) => {
+            for (key, child) in map {
+                if let Some(mut path) = find_marker(child, marker) {
+                    path.0.insert(0, PathSegment::Field(key.clone()));
+                    return Some(path);
+                }
+            }
+            None
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                if let Some(mut path) = find_marker(child, marker) {
+                    path.0.insert(0, PathSegment::Index(index));
+                    return Some(path);
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Locates the `{{text}}`/`{{embedding}}`-style placeholder in `template`.
+pub fn find_placeholder(template: &Value, marker: &str) -> Option<JsonPath> {
+    find_marker(template, marker)
+}
+
+/// Writes `new_value` at `path` inside `value`, cloning the template first is
+/// the caller's responsibility.
+pub fn set_at_path(value: &mut Value, path: &JsonPath, new_value: Value) -> Option<()> {
+    let mut cursor = value;
+    for segment in &path.0 {
+        cursor = match segment {
+            PathSegment::Field(key) => cursor.get_mut(key)?,
+            PathSegment::Index(index) => cursor.get_mut(*index)?,
+        };
+    }
+    *cursor = new_value;
+    Some(())
+}
+
+/// Reads the value stored at `path` inside `value`.
+pub fn get_at_path<'a>(value: &'a Value, path: &JsonPath) -> Option<&'a Value> {
+    let mut cursor = value;
+    for segment in &path.0 {
+        cursor = match segment {
+            PathSegment::Field(key) => cursor.get(key)?,
+            PathSegment::Index(index) => cursor.get(*index)?,
+        };
+    }
+    Some(cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_find_and_set_nested_field() {
+        let template = json!({ "model": "x", "input": { "text": "{{text}}" } });
+        let path = find_placeholder(&template, "{{text}}").expect("marker found");
+
+        let mut populated = template.clone();
+        set_at_path(&mut populated, &path, json!("hello world"));
+
+        assert_eq!(populated["input"]["text"], json!("hello world"));
+    }
+
+    #[test]
+    fn test_find_marker_inside_array() {
+        let template = json!({ "messages": [{ "role": "user", "content": "{{text}}" }] });
+        let path = find_placeholder(&template, "{{text}}").expect("marker found");
+
+        assert_eq!(
+            get_at_path(&template, &path),
+            Some(&json!("{{text}}"))
+        );
+    }
+
+    #[test]
+    fn test_missing_marker_returns_none() {
+        let template = json!({ "model": "x" });
+        assert!(find_placeholder(&template, "{{text}}").is_none());
+    }
+}
@@ -2,6 +2,7 @@ use actix_cors::Cors;
 use actix_web::{middleware::Logger, web, App, HttpResponse, HttpServer, Responder};
 use log::{error, info};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 mod concepts;
@@ -9,16 +10,60 @@ mod data;
 mod dimensionality;
 mod embeddings;
 mod error;
+mod retry;
+mod search;
 
-use crate::concepts::ConceptsModel;
+use crate::concepts::{Concept, ConceptsModel};
 use crate::data::client::DatabaseClient;
-use crate::embeddings::EmbeddingModel;
+use crate::data::ConceptRepo;
+use crate::embeddings::{provider_from_env, EmbeddingBatcher, EmbeddingProvider, Embedding};
 use crate::error::ApiError;
 
+/// Embeds `new_concepts`, skipping any whose content hash (concept text +
+/// embedding model id) already has a cached embedding in `cache` so
+/// identical concepts from an earlier upload are never re-embedded.
+async fn embed_new_concepts(
+    embedding_model: &dyn EmbeddingProvider,
+    new_concepts: &[Concept],
+    cache: &HashMap<String, Embedding>,
+) -> Result<Vec<Embedding>, ApiError> {
+    let model_id = embedding_model.id();
+    let mut embeddings: Vec<Option<Embedding>> = Vec::with_capacity(new_concepts.len());
+    let mut uncached_texts = Vec::new();
+    let mut uncached_indices = Vec::new();
+
+    for (index, concept) in new_concepts.iter().enumerate() {
+        let hash = DatabaseClient::content_hash(&concept.concept, model_id);
+        match cache.get(&hash) {
+            Some(embedding) => embeddings.push(Some(embedding.clone())),
+            None => {
+                embeddings.push(None);
+                uncached_texts.push(concept.concept.clone());
+                uncached_indices.push(index);
+            }
+        }
+    }
+
+    if !uncached_texts.is_empty() {
+        let freshly_embedded = embedding_model.get_batch_embeddings(&uncached_texts).await?;
+        if freshly_embedded.len() != uncached_texts.len() {
+            return Err(ApiError::EmbeddingGenerationError);
+        }
+        for (index, embedding) in uncached_indices.into_iter().zip(freshly_embedded) {
+            embeddings[index] = Some(embedding);
+        }
+    }
+
+    Ok(embeddings
+        .into_iter()
+        .map(|embedding| embedding.expect("every concept is either cached or freshly embedded"))
+        .collect())
+}
+
 struct AppState {
     concepts_model: Arc<ConceptsModel>,
-    embedding_model: Arc<EmbeddingModel>,
-    db_client: Arc<DatabaseClient>,
+    embedding_model: Arc<dyn EmbeddingProvider>,
+    db_client: Arc<dyn ConceptRepo>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,12 +93,18 @@ async fn process_text(
 
     let mut all_concepts = new_concepts.clone();
     let mut existing_embeddings = Vec::new();
+    let mut embedding_cache: HashMap<String, Embedding> = HashMap::new();
 
     // If user_id is provided, get existing concepts from database
     if let Some(user_id) = &data.user_id {
         info!("Loading existing concepts for user: {}", user_id);
 
-        let user_concepts = state.db_client.get_user_concepts(user_id).await?;
+        let user_concepts = state
+            .db_client
+            .get_user_concepts(user_id, state.embedding_model.id())
+            .await?;
+
+        embedding_cache = DatabaseClient::embeddings_by_hash(&user_concepts, state.embedding_model.id());
 
         // Combine existing concepts with new ones
         for (concept, embedding) in user_concepts {
@@ -62,20 +113,10 @@ async fn process_text(
         }
     }
 
-    // Get concept strings for embeddings
-    let concept_strings: Vec<String> = all_concepts.iter().map(|c| c.concept.clone()).collect();
-
-    // Generate embeddings for new concepts only
-    let new_concept_strings: Vec<String> = new_concepts.iter().map(|c| c.concept.clone()).collect();
-
-    let new_embeddings = state
-        .embedding_model
-        .get_batch_embeddings(&new_concept_strings)
-        .await?;
-
-    if new_embeddings.len() != new_concepts.len() {
-        return Err(ApiError::EmbeddingGenerationError);
-    }
+    // Embed new concepts, reusing a cached embedding wherever the same
+    // concept text was already embedded by this model for this user.
+    let new_embeddings =
+        embed_new_concepts(state.embedding_model.as_ref(), &new_concepts, &embedding_cache).await?;
 
     // Save new concepts to database asynchronously
     if let Some(user_id) = &data.user_id {
@@ -83,11 +124,15 @@ async fn process_text(
         let user_id = user_id.clone();
         let new_concepts_clone = new_concepts.clone();
         let new_embeddings_clone = new_embeddings.clone();
+        let model_id = state.embedding_model.id().to_string();
 
         // Spawn a task to save concepts without waiting for completion
         tokio::spawn(async move {
             for (concept, embedding) in new_concepts_clone.iter().zip(new_embeddings_clone.iter()) {
-                if let Err(e) = db_client.save_concept(&user_id, concept, embedding).await {
+                if let Err(e) = db_client
+                    .save_concept(&user_id, concept, embedding, &model_id)
+                    .await
+                {
                     error!("Failed to save concept: {:?}", e);
                 }
             }
@@ -99,7 +144,11 @@ async fn process_text(
     all_embeddings.extend(existing_embeddings);
 
     // Cluster concepts with embeddings
-    let clustered_results = dimensionality::cluster_concepts(&all_concepts, &all_embeddings)?;
+    let clustered_results = dimensionality::cluster_concepts(
+        &all_concepts,
+        &all_embeddings,
+        state.embedding_model.similarity_calibration(),
+    )?;
 
     let response = ApiResponse {
         success: true,
@@ -139,11 +188,17 @@ async fn upload_file(
 
     let mut all_concepts: Vec<concepts::Concept> = new_concepts.clone();
     let mut existing_embeddings: Vec<ndarray::ArrayBase<ndarray::OwnedRepr<f32>, ndarray::Dim<[usize; 1]>>> = Vec::new();
+    let mut embedding_cache: HashMap<String, Embedding> = HashMap::new();
 
     if let Some(user_id) = &query.user_id {
         info!("Loading existing concepts for user: {}", user_id);
 
-        let user_concepts = state.db_client.get_user_concepts(user_id).await?;
+        let user_concepts = state
+            .db_client
+            .get_user_concepts(user_id, state.embedding_model.id())
+            .await?;
+
+        embedding_cache = DatabaseClient::embeddings_by_hash(&user_concepts, state.embedding_model.id());
 
         for (concept, embedding) in user_concepts {
             all_concepts.push(concept);
@@ -151,28 +206,24 @@ async fn upload_file(
         }
     }
 
-    let concept_strings: Vec<String> = all_concepts.iter().map(|c| c.concept.clone()).collect();
-
-    let new_concept_strings: Vec<String> = new_concepts.iter().map(|c| c.concept.clone()).collect();
-
-    let new_embeddings = state
-        .embedding_model
-        .get_batch_embeddings(&new_concept_strings)
-        .await?;
-
-    if new_embeddings.len() != new_concepts.len() {
-        return Err(ApiError::EmbeddingGenerationError);
-    }
+    // Embed new concepts, reusing a cached embedding wherever the same
+    // concept text was already embedded by this model for this user.
+    let new_embeddings =
+        embed_new_concepts(state.embedding_model.as_ref(), &new_concepts, &embedding_cache).await?;
 
     if let Some(user_id) = &query.user_id {
         let db_client = Arc::clone(&state.db_client);
         let user_id = user_id.clone();
         let new_concepts_clone = new_concepts.clone();
         let new_embeddings_clone = new_embeddings.clone();
+        let model_id = state.embedding_model.id().to_string();
 
         tokio::spawn(async move {
             for (concept, embedding) in new_concepts_clone.iter().zip(new_embeddings_clone.iter()) {
-                if let Err(e) = db_client.save_concept(&user_id, concept, embedding).await {
+                if let Err(e) = db_client
+                    .save_concept(&user_id, concept, embedding, &model_id)
+                    .await
+                {
                     error!("Failed to save concept: {:?}", e);
                 }
             }
@@ -182,7 +233,11 @@ async fn upload_file(
     let mut all_embeddings = new_embeddings;
     all_embeddings.extend(existing_embeddings);
 
-    let clustered_results = dimensionality::cluster_concepts(&all_concepts, &all_embeddings)?;
+    let clustered_results = dimensionality::cluster_concepts(
+        &all_concepts,
+        &all_embeddings,
+        state.embedding_model.similarity_calibration(),
+    )?;
 
     let response = ApiResponse {
         success: true,
@@ -192,7 +247,102 @@ async fn upload_file(
     Ok(HttpResponse::Ok().json(response))
 }
 
-async fn preload_models(concepts_model: &ConceptsModel, embedding_model: &EmbeddingModel) {
+#[derive(Debug, Deserialize)]
+struct SearchRequest {
+    text: String,
+    user_id: String,
+    top_k: usize,
+    /// Biases hybrid ranking toward keyword (0.0) or vector (1.0) results;
+    /// defaults to weighing both lists equally. Ignored when `semantic_only`
+    /// is set.
+    #[serde(default = "default_semantic_ratio")]
+    semantic_ratio: f32,
+    /// Skips keyword extraction and RRF fusion entirely and ranks purely by
+    /// cosine similarity via `ConceptRepo::search_similar_concepts`, which
+    /// is cheaper than hybrid search when the caller has no interest in
+    /// keyword overlap.
+    #[serde(default)]
+    semantic_only: bool,
+}
+
+fn default_semantic_ratio() -> f32 {
+    search::DEFAULT_SEMANTIC_RATIO
+}
+
+#[derive(Debug, Serialize)]
+struct SearchResult {
+    concept: Concept,
+    fused_score: f32,
+    lexical_rank: Option<usize>,
+    semantic_rank: Option<usize>,
+}
+
+async fn search(
+    data: web::Json<SearchRequest>,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, ApiError> {
+    info!(
+        "Searching {} top concepts for user {} matching: {}",
+        data.top_k, data.user_id, data.text
+    );
+
+    let query_embedding = state
+        .embedding_model
+        .get_contextual_embeddings(&data.text)
+        .await?;
+    let normalized_query = search::normalize(&query_embedding);
+
+    let results: Vec<SearchResult> = if data.semantic_only {
+        state
+            .db_client
+            .search_similar_concepts(
+                &data.user_id,
+                state.embedding_model.id(),
+                &normalized_query,
+                data.top_k,
+            )
+            .await?
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (concept, similarity))| SearchResult {
+                concept,
+                fused_score: similarity,
+                lexical_rank: None,
+                semantic_rank: Some(rank + 1),
+            })
+            .collect()
+    } else {
+        state
+            .db_client
+            .search_user_concepts(
+                &data.user_id,
+                state.embedding_model.id(),
+                &data.text,
+                &normalized_query,
+                data.top_k,
+                data.semantic_ratio,
+                state.embedding_model.similarity_calibration(),
+            )
+            .await?
+            .into_iter()
+            .map(|hit| SearchResult {
+                concept: hit.concept,
+                fused_score: hit.fused_score,
+                lexical_rank: hit.lexical_rank,
+                semantic_rank: hit.semantic_rank,
+            })
+            .collect()
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: results,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn preload_models(concepts_model: &ConceptsModel, embedding_model: &dyn EmbeddingProvider) {
     info!("Preloading models...");
     if let Err(e) = concepts_model.generate_concepts("Preloading...").await {
         info!("Error preloading concepts model: {:?}", e);
@@ -221,16 +371,24 @@ async fn main() -> std::io::Result<()> {
     info!("Using Database nodes: {}", db_nodes);
 
     let concepts_model = Arc::new(ConceptsModel::new(&ollama_base_url));
-    let embedding_model = Arc::new(EmbeddingModel::new(&ollama_base_url));
+    let embedding_model: Arc<dyn EmbeddingProvider> =
+        Arc::new(EmbeddingBatcher::new(provider_from_env(&ollama_base_url)));
 
     let db_nodes: Vec<&str> = db_nodes.split(',').collect();
-    let db_client = Arc::new(
-        DatabaseClient::new(&db_nodes)
+    let db_username = std::env::var("DB_USERNAME").ok();
+    let db_password = std::env::var("DB_PASSWORD").ok();
+    let db_auth = db_username
+        .as_deref()
+        .zip(db_password.as_deref());
+    let db_keyspace = std::env::var("DB_KEYSPACE").ok();
+
+    let db_client: Arc<dyn ConceptRepo> = Arc::new(
+        DatabaseClient::new(&db_nodes, db_auth, db_keyspace.as_deref(), None)
             .await
             .expect("Failed to connect to database"),
     );
 
-    preload_models(&concepts_model, &embedding_model).await;
+    preload_models(&concepts_model, embedding_model.as_ref()).await;
 
     let app_state = web::Data::new(AppState {
         concepts_model,
@@ -251,6 +409,7 @@ async fn main() -> std::io::Result<()> {
             .app_data(app_state.clone())
             .route("/api/vectorize", web::post().to(process_text))
             .route("/api/upload", web::post().to(upload_file))
+            .route("/api/search", web::post().to(search))
     })
     .bind((host, port))?
     .run()
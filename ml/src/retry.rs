@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+/// Default ceiling on retry attempts for a single logical request.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+
+/// What to do after a failed attempt, decided from the HTTP status/body of
+/// the failure. Callers loop on this until `GiveUp` or success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// The error is not transient (e.g. 4xx other than rate-limit); stop.
+    GiveUp,
+    /// A generic transient error (e.g. 5xx, connection reset); back off and retry.
+    Retry,
+    /// The server signaled a rate limit (429 or a body mentioning it); back off
+    /// a little longer before retrying.
+    RetryAfterRateLimit,
+    /// The server rejected the request because the prompt was too long; the
+    /// caller should resubmit a truncated/segmented version almost immediately.
+    RetryTokenized,
+}
+
+/// Classifies a failed HTTP response into a `RetryDecision` using its status
+/// code and, for ambiguous cases, a snippet of the response body.
+pub fn classify_error(status: Option<reqwest::StatusCode>, body: &str) -> RetryDecision {
+    let lower_body = body.to_lowercase();
+
+    if lower_body.contains("too long")
+        || lower_body.contains("context length")
+        || lower_body.contains("token limit")
+        || lower_body.contains("maximum context")
+    {
+        return RetryDecision::RetryTokenized;
+    }
+
+    match status {
+        Some(status) if status == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            RetryDecision::RetryAfterRateLimit
+        }
+        Some(status) if lower_body.contains("rate limit") || lower_body.contains("rate_limit") => {
+            let _ = status;
+            RetryDecision::RetryAfterRateLimit
+        }
+        Some(status) if status.is_client_error() => RetryDecision::GiveUp,
+        Some(status) if status.is_server_error() => RetryDecision::Retry,
+        None => RetryDecision::Retry,
+        Some(_) => RetryDecision::Retry,
+    }
+}
+
+/// Computes the backoff delay for a given attempt (1-indexed) and decision.
+///
+/// Generic failures back off exponentially as `10^attempt` milliseconds.
+/// Rate-limited failures add a `100ms` floor on top of the same curve so we
+/// don't hammer a server that just told us to slow down. A tokenization
+/// retry is a local reshaping of the request, not a wait on the server, so
+/// it uses a near-zero delay instead.
+pub fn backoff_delay(attempt: u32, decision: RetryDecision) -> Duration {
+    match decision {
+        RetryDecision::RetryTokenized => Duration::from_millis(1),
+        RetryDecision::RetryAfterRateLimit => {
+            Duration::from_millis(100 + 10u64.saturating_pow(attempt))
+        }
+        RetryDecision::Retry => Duration::from_millis(10u64.saturating_pow(attempt)),
+        RetryDecision::GiveUp => Duration::from_millis(0),
+    }
+}
+
+/// Classifies a Cassandra driver error into a `RetryDecision`. `cdrs_tokio`
+/// surfaces errors as opaque, already-formatted strings rather than
+/// structured status codes, so classification looks for the same signal
+/// words the server/driver use for overload and transient failures. Never
+/// returns `RetryTokenized`, which only applies to oversized LLM prompts.
+pub fn classify_db_error(message: &str) -> RetryDecision {
+    let lower = message.to_lowercase();
+
+    if lower.contains("overloaded")
+        || lower.contains("rate limit")
+        || lower.contains("too many requests")
+    {
+        RetryDecision::RetryAfterRateLimit
+    } else if lower.contains("timeout")
+        || lower.contains("timed out")
+        || lower.contains("unavailable")
+        || lower.contains("connection")
+        || lower.contains("no connections")
+    {
+        RetryDecision::Retry
+    } else {
+        RetryDecision::GiveUp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_rate_limit_status() {
+        let decision = classify_error(Some(reqwest::StatusCode::TOO_MANY_REQUESTS), "");
+        assert_eq!(decision, RetryDecision::RetryAfterRateLimit);
+    }
+
+    #[test]
+    fn test_classify_too_long_body() {
+        let decision = classify_error(
+            Some(reqwest::StatusCode::BAD_REQUEST),
+            "prompt is too long for this model's context length",
+        );
+        assert_eq!(decision, RetryDecision::RetryTokenized);
+    }
+
+    #[test]
+    fn test_classify_server_error_retries() {
+        let decision = classify_error(Some(reqwest::StatusCode::BAD_GATEWAY), "upstream down");
+        assert_eq!(decision, RetryDecision::Retry);
+    }
+
+    #[test]
+    fn test_classify_client_error_gives_up() {
+        let decision = classify_error(Some(reqwest::StatusCode::NOT_FOUND), "not found");
+        assert_eq!(decision, RetryDecision::GiveUp);
+    }
+
+    #[test]
+    fn test_backoff_tokenized_is_near_zero() {
+        assert_eq!(
+            backoff_delay(3, RetryDecision::RetryTokenized),
+            Duration::from_millis(1)
+        );
+    }
+
+    #[test]
+    fn test_backoff_rate_limit_adds_floor() {
+        let generic = backoff_delay(2, RetryDecision::Retry);
+        let rate_limited = backoff_delay(2, RetryDecision::RetryAfterRateLimit);
+        assert_eq!(rate_limited, generic + Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_classify_db_error_overload_rate_limits() {
+        assert_eq!(
+            classify_db_error("server error: overloaded"),
+            RetryDecision::RetryAfterRateLimit
+        );
+    }
+
+    #[test]
+    fn test_classify_db_error_timeout_retries() {
+        assert_eq!(
+            classify_db_error("Query error: operation timed out"),
+            RetryDecision::Retry
+        );
+    }
+
+    #[test]
+    fn test_classify_db_error_other_gives_up() {
+        assert_eq!(
+            classify_db_error("Invalid UUID: invalid length"),
+            RetryDecision::GiveUp
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::concepts::nlp::{CandidateKeyword, KeywordExtractor};
+use crate::concepts::Concept;
+use crate::embeddings::calibration::{calibrate, SimilarityCalibration};
+use crate::embeddings::Embedding;
+use serde::Serialize;
+
+/// Normalizes `embedding` to a unit vector, guarding against a zero norm.
+/// Once every embedding is unit-length, cosine similarity reduces to a
+/// plain dot product.
+pub fn normalize(embedding: &Embedding) -> Embedding {
+    let norm = embedding.dot(embedding).sqrt();
+    if norm == 0.0 {
+        return embedding.clone();
+    }
+    embedding / norm
+}
+
+/// Scores every `(Concept, Embedding)` candidate against a (already unit
+/// normalized) query embedding and returns the top `top_k` by cosine
+/// similarity, descending.
+pub fn top_k_similar(
+    query: &Embedding,
+    candidates: &[(Concept, Embedding)],
+    top_k: usize,
+    calibration: Option<&SimilarityCalibration>,
+) -> Vec<(Concept, f32)> {
+    let mut scored: Vec<(Concept, f32)> = candidates
+        .iter()
+        .map(|(concept, embedding)| {
+            let score = calibrate(query.dot(&normalize(embedding)), calibration);
+            (concept.clone(), score)
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(top_k);
+    scored
+}
+
+/// Constant `k` in Reciprocal Rank Fusion, following the usual TREC/MSMARCO
+/// choice: high enough that a single list's top hit doesn't dominate the
+/// fused score, low enough that rank still matters more than raw score.
+pub const DEFAULT_RRF_K: f32 = 60.0;
+
+/// Default weight given to the semantic list in [`hybrid_search`] when the
+/// caller doesn't bias toward keyword or vector results.
+pub const DEFAULT_SEMANTIC_RATIO: f32 = 0.5;
+
+/// A hybrid search hit with enough detail to see why it ranked where it
+/// did: its fused score plus its 1-based rank in each contributing list
+/// (`None` if it didn't appear in that list at all).
+#[derive(Debug, Clone, Serialize)]
+pub struct HybridSearchResult {
+    pub concept: Concept,
+    pub fused_score: f32,
+    pub lexical_rank: Option<usize>,
+    pub semantic_rank: Option<usize>,
+}
+
+/// Scores each candidate's lexical overlap with `keywords` (extracted from
+/// the query text) as the sum of the scores of every keyword phrase that
+/// appears in the concept's text. Candidates with no overlap score 0 and
+/// are dropped from the returned ranking.
+fn lexical_ranked(
+    keywords: &[CandidateKeyword],
+    candidates: &[(Concept, Embedding)],
+) -> Vec<(usize, f32)> {
+    let mut scored: Vec<(usize, f32)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (concept, _))| {
+            let concept_text = concept.concept.to_lowercase();
+            let score: f32 = keywords
+                .iter()
+                .filter(|keyword| concept_text.contains(&keyword.phrase))
+                .map(|keyword| keyword.score)
+                .sum();
+
+            (score > 0.0).then_some((index, score))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+}
+
+/// Looks up `index`'s 1-based position in a list already sorted by
+/// descending score, or `None` if it isn't in the list.
+fn rank_of(ranked: &[(usize, f32)], index: usize) -> Option<usize> {
+    ranked.iter().position(|(i, _)| *i == index).map(|pos| pos + 1)
+}
+
+/// Scores every candidate against the (already unit-normalized) query
+/// embedding and returns every index ranked by descending cosine
+/// similarity, mirroring [`top_k_similar`] but keeping each candidate's
+/// index into `candidates` instead of cloning the `Concept`.
+fn semantic_ranked(
+    query: &Embedding,
+    candidates: &[(Concept, Embedding)],
+    calibration: Option<&SimilarityCalibration>,
+) -> Vec<(usize, f32)> {
+    let mut scored: Vec<(usize, f32)> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, (_, embedding))| {
+            (index, calibrate(query.dot(&normalize(embedding)), calibration))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+}
+
+/// Reciprocal Rank Fusion term for a rank, weighted by `weight`: `0` when
+/// the item didn't appear in the list.
+fn rrf_term(rank: Option<usize>, weight: f32, k: f32) -> f32 {
+    rank.map_or(0.0, |rank| weight / (k + rank as f32))
+}
+
+/// Blends lexical keyword matching (via [`KeywordExtractor::extract_candidates`])
+/// and embedding cosine similarity into a single ranking using Reciprocal
+/// Rank Fusion: `score(c) = Σ_lists weight / (k + rank_list(c))`, where a
+/// concept absent from a list contributes nothing for that term.
+/// `semantic_ratio` (0.0-1.0) biases the fusion toward the lexical list
+/// (0.0) or the semantic list (1.0); [`DEFAULT_SEMANTIC_RATIO`] weighs them
+/// equally. `query_embedding` must already be unit-normalized.
+pub fn hybrid_search(
+    query_text: &str,
+    query_embedding: &Embedding,
+    candidates: &[(Concept, Embedding)],
+    top_k: usize,
+    semantic_ratio: f32,
+    calibration: Option<&SimilarityCalibration>,
+) -> Vec<HybridSearchResult> {
+    let semantic_ratio = semantic_ratio.clamp(0.0, 1.0);
+    let semantic_weight = semantic_ratio;
+    let lexical_weight = 1.0 - semantic_ratio;
+
+    let keywords = KeywordExtractor::new().extract_candidates(query_text, 20);
+    let lexical = lexical_ranked(&keywords, candidates);
+    let semantic = semantic_ranked(query_embedding, candidates, calibration);
+
+    let mut results: Vec<HybridSearchResult> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, (concept, _))| {
+            let lexical_rank = rank_of(&lexical, index);
+            let semantic_rank = rank_of(&semantic, index);
+            let fused_score = rrf_term(lexical_rank, lexical_weight, DEFAULT_RRF_K)
+                + rrf_term(semantic_rank, semantic_weight, DEFAULT_RRF_K);
+
+            HybridSearchResult {
+                concept: concept.clone(),
+                fused_score,
+                lexical_rank,
+                semantic_rank,
+            }
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.fused_score
+            .partial_cmp(&a.fused_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    results.truncate(top_k);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_normalize_unit_length() {
+        let embedding = array![3.0, 4.0];
+        let normalized = normalize(&embedding);
+        assert!((normalized.dot(&normalized).sqrt() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_normalize_zero_vector_is_unchanged() {
+        let embedding = array![0.0, 0.0];
+        let normalized = normalize(&embedding);
+        assert_eq!(normalized, embedding);
+    }
+
+    #[test]
+    fn test_top_k_orders_by_similarity() {
+        let query = normalize(&array![1.0, 0.0]);
+        let candidates = vec![
+            (
+                Concept {
+                    concept: "orthogonal".to_string(),
+                    source_range: None,
+                },
+                array![0.0, 1.0],
+            ),
+            (
+                Concept {
+                    concept: "aligned".to_string(),
+                    source_range: None,
+                },
+                array![2.0, 0.0],
+            ),
+        ];
+
+        let results = top_k_similar(&query, &candidates, 2, None);
+        assert_eq!(results[0].0.concept, "aligned");
+        assert!(results[0].1 > results[1].1);
+    }
+
+    #[test]
+    fn test_rank_of_finds_position() {
+        let ranked = vec![(3, 0.9), (1, 0.5), (0, 0.1)];
+        assert_eq!(rank_of(&ranked, 1), Some(2));
+        assert_eq!(rank_of(&ranked, 42), None);
+    }
+
+    #[test]
+    fn test_rrf_term_weights_and_missing_rank() {
+        assert_eq!(rrf_term(None, 1.0, DEFAULT_RRF_K), 0.0);
+        let term = rrf_term(Some(1), 0.5, DEFAULT_RRF_K);
+        assert!((term - 0.5 / (DEFAULT_RRF_K + 1.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hybrid_search_fuses_lexical_and_semantic() {
+        let query_text = "Machine learning is transforming the industry. \
+                           Machine learning models are used everywhere today.";
+        let query_embedding = normalize(&array![1.0, 0.0]);
+
+        let candidates = vec![
+            (
+                Concept {
+                    concept: "machine learning basics".to_string(),
+                    source_range: None,
+                },
+                array![1.0, 0.0],
+            ),
+            (
+                Concept {
+                    concept: "gardening tips".to_string(),
+                    source_range: None,
+                },
+                array![0.0, 1.0],
+            ),
+        ];
+
+        let results = hybrid_search(
+            query_text,
+            &query_embedding,
+            &candidates,
+            2,
+            DEFAULT_SEMANTIC_RATIO,
+            None,
+        );
+
+        assert_eq!(results[0].concept.concept, "machine learning basics");
+        assert!(results[0].lexical_rank.is_some());
+        assert!(results[0].semantic_rank.is_some());
+        assert!(results[1].lexical_rank.is_none());
+    }
+}